@@ -0,0 +1,121 @@
+use bevy::{
+    prelude::*,
+    ecs::system::RunSystemOnce,
+    math::bounding::{Aabb2d, IntersectsVolume},
+};
+use criterion::{
+    BenchmarkId,
+    Criterion,
+    criterion_group,
+    criterion_main,
+};
+use rand::{
+    Rng,
+    SeedableRng,
+    rngs::StdRng,
+};
+use std::hint::black_box;
+use ittoku_2dshooting_game::{
+    WINDOW_SIZE,
+    headless::HeadlessApp,
+    ingame::Pool,
+    ingame::SpatialGrid,
+    ingame::enemy::BulletType,
+    ingame::enemy::bullet::{
+        BulletImages,
+        spawn_bullet,
+    },
+};
+
+const SEED: u64 = 42;
+const BULLET_COUNTS: [usize; 3] = [1_000, 4_000, 8_000];
+const ENEMY_COUNTS: [usize; 2] = [16, 256];
+const BULLET_SIZE: Vec2 = Vec2::new(8.0, 32.0);
+const ENEMY_SIZE: Vec2 = Vec2::new(32.0, 32.0);
+const WARMUP_TICKS: u32 = 10;
+
+fn boxes(rng: &mut StdRng, count: usize, size: Vec2) -> Vec<Aabb2d> {
+    (0..count)
+        .map(|_| {
+            let position = Vec2::new(
+                rng.gen_range(-WINDOW_SIZE.x / 2.0..WINDOW_SIZE.x / 2.0),
+                rng.gen_range(-WINDOW_SIZE.y / 2.0..WINDOW_SIZE.y / 2.0),
+            );
+            Aabb2d::new(position, size / 2.0)
+        })
+        .collect()
+}
+
+// the old nested loops against a grid rebuilt from scratch, over the same bullets and enemies
+fn broadphase(c: &mut Criterion) {
+    let mut group = c.benchmark_group("broadphase");
+
+    for enemy_count in ENEMY_COUNTS {
+        for count in BULLET_COUNTS {
+            let mut rng = StdRng::seed_from_u64(SEED);
+            let bullets = boxes(&mut rng, count, BULLET_SIZE);
+            let enemies = boxes(&mut rng, enemy_count, ENEMY_SIZE);
+            let mut grid = SpatialGrid::default();
+            let parameter = format!("{}x{}", count, enemy_count);
+
+            group.bench_function(BenchmarkId::new("pairwise", &parameter), |b| {
+                b.iter(|| {
+                    bullets.iter()
+                        .map(|bullet| enemies.iter().filter(|enemy| bullet.intersects(*enemy)).count())
+                        .sum::<usize>()
+                })
+            });
+            group.bench_function(BenchmarkId::new("grid", &parameter), |b| {
+                b.iter(|| {
+                    grid.clear();
+                    for (index, aabb) in enemies.iter().enumerate() {
+                        grid.insert(Entity::from_raw(index as u32), *aabb);
+                    }
+                    bullets.iter()
+                        .map(|bullet| grid.query(*bullet).count())
+                        .sum::<usize>()
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+fn spawn_bullets(app: &mut HeadlessApp, count: usize) {
+    app.app_mut().world_mut().run_system_once(move |
+        mut commands: Commands,
+        mut pool: ResMut<Pool>,
+        images: Res<BulletImages>,
+    | {
+        let mut rng = StdRng::seed_from_u64(SEED);
+
+        // parked above the player so the stage keeps running while they are checked every tick
+        for _ in 0..count {
+            let origin = Vec2::new(
+                rng.gen_range(-WINDOW_SIZE.x / 2.0..WINDOW_SIZE.x / 2.0),
+                rng.gen_range(0.0..WINDOW_SIZE.y / 2.0),
+            );
+            spawn_bullet(&mut commands, &mut pool, &images, BulletType::Fighter, origin, Vec2::ZERO);
+        }
+    }).expect("bullet images are loaded on entering the stage");
+}
+
+// a whole fixed tick of the headless game with thousands of bullets on screen
+fn headless_tick(c: &mut Criterion) {
+    let mut group = c.benchmark_group("headless_tick");
+    group.sample_size(20);
+
+    for count in BULLET_COUNTS {
+        let mut app = HeadlessApp::with_seed(SEED);
+        app.step(WARMUP_TICKS);
+        spawn_bullets(&mut app, count);
+
+        group.bench_with_input(BenchmarkId::from_parameter(count), &count, |b, _| {
+            b.iter(|| black_box(app.step(1)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, broadphase, headless_tick);
+criterion_main!(benches);
@@ -0,0 +1,225 @@
+use bevy::{
+    prelude::*,
+    ecs::system::SystemParam,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::{
+    GameConfig,
+    storage,
+};
+
+const FILE_NAME: &str = "bindings.ron";
+pub const ACTIONS: [InputAction; 12] = [
+    InputAction::MoveUp,
+    InputAction::MoveDown,
+    InputAction::MoveLeft,
+    InputAction::MoveRight,
+    InputAction::Shoot,
+    InputAction::Bomb,
+    InputAction::Pause,
+    InputAction::Confirm,
+    InputAction::Start,
+    InputAction::Retry,
+    InputAction::BackToTitle,
+    InputAction::Options,
+];
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Shoot,
+    Bomb,
+    Pause,
+    Confirm,
+    Start,
+    Retry,
+    BackToTitle,
+    Options,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButton),
+}
+
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct InputBindings {
+    bindings: Vec<(InputAction, Vec<Binding>)>,
+    #[serde(skip)]
+    persistent: bool,
+}
+
+#[derive(SystemParam)]
+pub struct Actions<'w, 's> {
+    bindings: Res<'w, InputBindings>,
+    keyboard_input: Res<'w, ButtonInput<KeyCode>>,
+    mouse_input: Res<'w, ButtonInput<MouseButton>>,
+    gamepads: Query<'w, 's, &'static Gamepad>,
+}
+
+impl InputAction {
+    pub fn key(&self) -> &'static str {
+        match self {
+            InputAction::MoveUp => "action.move_up",
+            InputAction::MoveDown => "action.move_down",
+            InputAction::MoveLeft => "action.move_left",
+            InputAction::MoveRight => "action.move_right",
+            InputAction::Shoot => "action.shoot",
+            InputAction::Bomb => "action.bomb",
+            InputAction::Pause => "action.pause",
+            InputAction::Confirm => "action.confirm",
+            InputAction::Start => "action.start",
+            InputAction::Retry => "action.retry",
+            InputAction::BackToTitle => "action.back_to_title",
+            InputAction::Options => "action.options",
+        }
+    }
+
+    fn defaults(&self) -> Vec<Binding> {
+        use Binding::*;
+
+        match self {
+            InputAction::MoveUp => vec![Key(KeyCode::KeyW), Key(KeyCode::ArrowUp), Gamepad(GamepadButton::DPadUp)],
+            InputAction::MoveDown => vec![Key(KeyCode::KeyS), Key(KeyCode::ArrowDown), Gamepad(GamepadButton::DPadDown)],
+            InputAction::MoveLeft => vec![Key(KeyCode::KeyA), Key(KeyCode::ArrowLeft), Gamepad(GamepadButton::DPadLeft)],
+            InputAction::MoveRight => vec![Key(KeyCode::KeyD), Key(KeyCode::ArrowRight), Gamepad(GamepadButton::DPadRight)],
+            InputAction::Shoot => vec![Key(KeyCode::Space), Gamepad(GamepadButton::South)],
+            InputAction::Bomb => vec![Key(KeyCode::KeyX), Gamepad(GamepadButton::East)],
+            InputAction::Pause => vec![Key(KeyCode::Escape), Gamepad(GamepadButton::Start)],
+            InputAction::Confirm => vec![Key(KeyCode::Enter), Key(KeyCode::Space), Gamepad(GamepadButton::South)],
+            InputAction::Start => vec![Mouse(MouseButton::Left), Key(KeyCode::Enter), Gamepad(GamepadButton::South), Gamepad(GamepadButton::Start)],
+            InputAction::Retry => vec![Key(KeyCode::KeyR), Gamepad(GamepadButton::South)],
+            InputAction::BackToTitle => vec![Key(KeyCode::KeyB), Gamepad(GamepadButton::East)],
+            InputAction::Options => vec![Key(KeyCode::KeyO), Gamepad(GamepadButton::Select)],
+        }
+    }
+}
+
+impl Binding {
+    pub fn text(&self) -> String {
+        match self {
+            Binding::Key(key) => {
+                let name = format!("{:?}", key);
+                name.strip_prefix("Key").filter(|name| !name.is_empty()).map_or(name.clone(), str::to_string)
+            }
+            Binding::Mouse(button) => format!("Mouse{:?}", button),
+            Binding::Gamepad(button) => format!("Pad{:?}", button),
+        }
+    }
+
+    fn is_gamepad(&self) -> bool { matches!(self, Binding::Gamepad(_)) }
+}
+
+impl InputBindings {
+    fn load(persistent: bool) -> Self {
+        let mut bindings = persistent
+            .then(|| storage::load_config(FILE_NAME))
+            .flatten()
+            .and_then(|contents| match ron::from_str::<Self>(&contents) {
+                Ok(bindings) => Some(bindings),
+                Err(err) => {
+                    warn!("failed to read bindings: {}", err);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        // actions missing from an older file keep their defaults
+        for action in ACTIONS {
+            if bindings.bindings.iter().any(|(other, _)| *other == action) { continue }
+            bindings.bindings.push((action, action.defaults()));
+        }
+        bindings.persistent = persistent;
+        bindings
+    }
+
+    pub fn get(&self, action: InputAction) -> &[Binding] {
+        self.bindings.iter()
+            .find(|(other, _)| *other == action)
+            .map_or(&[], |(_, bindings)| bindings.as_slice())
+    }
+
+    // a new binding replaces the ones from the same kind of device
+    pub fn bind(&mut self, action: InputAction, binding: Binding) {
+        match self.bindings.iter_mut().find(|(other, _)| *other == action) {
+            Some((_, bindings)) => {
+                bindings.retain(|other| other.is_gamepad() != binding.is_gamepad());
+                bindings.push(binding);
+            }
+            None => self.bindings.push((action, vec![binding])),
+        }
+        self.save();
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self { persistent: self.persistent, ..Default::default() };
+        self.save();
+    }
+
+    fn save(&self) {
+        if !self.persistent { return }
+
+        let result = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())
+            .and_then(|contents| storage::save_config(FILE_NAME, &contents).map_err(|err| err.to_string()));
+
+        if let Err(err) = result { warn!("failed to save bindings: {}", err) }
+    }
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self {
+            bindings: ACTIONS.iter().map(|action| (*action, action.defaults())).collect(),
+            persistent: false,
+        }
+    }
+}
+
+impl Actions<'_, '_> {
+    pub fn pressed(&self, action: InputAction) -> bool {
+        self.bindings.get(action).iter().any(|binding| match binding {
+            Binding::Key(key) => self.keyboard_input.pressed(*key),
+            Binding::Mouse(button) => self.mouse_input.pressed(*button),
+            Binding::Gamepad(button) => self.gamepads.iter().any(|gamepad| gamepad.pressed(*button)),
+        })
+    }
+
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.bindings.get(action).iter().any(|binding| match binding {
+            Binding::Key(key) => self.keyboard_input.just_pressed(*key),
+            Binding::Mouse(button) => self.mouse_input.just_pressed(*button),
+            Binding::Gamepad(button) => self.gamepads.iter().any(|gamepad| gamepad.just_pressed(*button)),
+        })
+    }
+
+    pub fn axis(&self, negative: InputAction, positive: InputAction) -> f32 {
+        self.pressed(positive) as i32 as f32 - self.pressed(negative) as i32 as f32
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+) {
+    // debug!("setup");
+    commands.insert_resource(InputBindings::load(config.persistent));
+}
+
+pub struct ActionPlugin;
+
+impl Plugin for ActionPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(PreStartup, setup)
+        ;
+    }
+}
@@ -0,0 +1,52 @@
+use bevy::{
+    prelude::*,
+    diagnostic::{
+        Diagnostic,
+        DiagnosticPath,
+        Diagnostics,
+        RegisterDiagnostic,
+    },
+    utils::HashMap,
+};
+
+pub const ATLAS_LAYOUTS: DiagnosticPath = DiagnosticPath::const_new("atlas_layouts");
+
+// one layout per sprite sheet grid, shared by everything drawn from it
+#[derive(Resource, Default)]
+pub struct AtlasLayouts(HashMap<(UVec2, u32, u32), Handle<TextureAtlasLayout>>);
+
+impl AtlasLayouts {
+    pub fn grid(
+        &mut self,
+        texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+        tile_size: UVec2,
+        columns: u32,
+        rows: u32,
+    ) -> Handle<TextureAtlasLayout> {
+        self.0.entry((tile_size, columns, rows))
+            .or_insert_with(|| {
+                // debug!("grid: {} {}x{}", tile_size, columns, rows);
+                texture_atlas_layouts.add(TextureAtlasLayout::from_grid(tile_size, columns, rows, None, None))
+            })
+            .clone()
+    }
+}
+
+fn measure(
+    mut diagnostics: Diagnostics,
+    texture_atlas_layouts: Res<Assets<TextureAtlasLayout>>,
+) {
+    diagnostics.add_measurement(&ATLAS_LAYOUTS, || texture_atlas_layouts.len() as f64);
+}
+
+pub struct AtlasPlugin;
+
+impl Plugin for AtlasPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<AtlasLayouts>()
+            .register_diagnostic(Diagnostic::new(ATLAS_LAYOUTS))
+            .add_systems(Last, measure)
+        ;
+    }
+}
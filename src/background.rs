@@ -1,13 +1,14 @@
 use bevy::prelude::*;
 
 use crate::{
-    WINDOW_SIZE,
     AppState,
+    GameConfig,
     MyCamera,
 };
+use crate::atlas::AtlasLayouts;
+use crate::loading::GameAssets;
 
 const BACKGROUND_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
-const PATH_IMAGE: &str = "ittoku-2dshooting-game/background.png";
 const IMAGE_SIZE: UVec2 = UVec2::new(640, 480);
 const COLUMN: u32 = 9;
 const ROW: u32 = 1;
@@ -30,18 +31,19 @@ struct Mybackground;
 fn setup(
     mut commands: Commands,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
-    asset_server: Res<AssetServer>,
+    mut atlas_layouts: ResMut<AtlasLayouts>,
+    game_assets: Res<GameAssets>,
+    config: Res<GameConfig>,
 ) {
     // debug!("setup");
-    let images = asset_server.load(PATH_IMAGE);
+    let images = game_assets.background.clone();
+    let texture_atlas_layout = atlas_layouts.grid(&mut texture_atlas_layouts, IMAGE_SIZE, COLUMN, ROW);
 
     for i in 0..MAX_COUNT {
-        let layout = TextureAtlasLayout::from_grid(IMAGE_SIZE, COLUMN, ROW, None, None);
-        let texture_atlas_layout = texture_atlas_layouts.add(layout);
         let animation_indices = AnimationIndices { first: 0, last: 8, };
         let translation = Vec3::new(
             0.0,
-            WINDOW_SIZE.y * i as f32,
+            config.window_size.y * i as f32,
             -99.0,
         );
         // background
@@ -49,7 +51,7 @@ fn setup(
             Sprite::from_atlas_image(
                 images.clone(),
                 TextureAtlas {
-                    layout: texture_atlas_layout,
+                    layout: texture_atlas_layout.clone(),
                     index: animation_indices.first,
                 },
             ),
@@ -84,6 +86,7 @@ fn animation(
 fn check_offscreen(
     mut bg_query: Query<&mut Transform, (With<Mybackground>, Without<MyCamera>)>,
     camera_query: Query<&Transform, (With<MyCamera>, Without<Mybackground>)>,
+    config: Res<GameConfig>,
 ) {
     let Ok(camera_transform) = camera_query.get_single() else { return };
     let camera_y = (camera_transform.translation.y / 10.0).round() * 10.0;
@@ -91,23 +94,21 @@ fn check_offscreen(
     for mut bg_transform in &mut bg_query {
         let bg_y = bg_transform.translation.y;
 
-        if bg_y <= camera_y - WINDOW_SIZE.y + 5.0 {
+        if bg_y <= camera_y - config.window_size.y + 5.0 {
             // debug!("check_offscreen");
-            bg_transform.translation.y = camera_y + WINDOW_SIZE.y * 2.0;
+            bg_transform.translation.y = camera_y + config.window_size.y * 2.0;
             // trace!("background y: {}", bg_transform.translation.y);
         }
     }
 }
 
 fn reset_position(
-    mut query: Query<&mut Transform, With<Mybackground>>
+    mut query: Query<&mut Transform, With<Mybackground>>,
+    config: Res<GameConfig>,
 ) {
     // debug!("reset_position");
-    let mut i = 0;
-
-    for mut transform in &mut query {
-        transform.translation.y = WINDOW_SIZE.y * i as f32;
-        i += 1;
+    for (i, mut transform) in query.iter_mut().enumerate() {
+        transform.translation.y = config.window_size.y * i as f32;
     }
 }
 
@@ -122,7 +123,9 @@ impl Plugin for BackgroundPlugin {
                 animation,
                 check_offscreen,
             ))
-            .add_systems(OnExit(AppState::Gameover), reset_position)
+            .add_systems(OnExit(AppState::StageClear), reset_position)
+            .add_systems(OnEnter(AppState::Mainmenu), reset_position)
+            .add_systems(OnEnter(AppState::Restart), reset_position)
         ;
     }
 }
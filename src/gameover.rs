@@ -1,21 +1,52 @@
 use bevy::prelude::*;
 
 use crate::{
-    PATH_FONT,
     AppState,
     Score,
     MyCamera,
 };
-use crate::ingame::fighter::PATH_IMAGE_FIGHTER;
-use crate::ingame::torpedo::PATH_IMAGE_TORPEDO;
+use crate::action::{
+    Actions,
+    InputAction,
+};
+use crate::highscore::{
+    NAME_LENGTH,
+    HighScore,
+    HighScores,
+};
+use crate::locale::{
+    Localized,
+    Strings,
+};
+use crate::ingame::enemy::{
+    EnemyDefinition,
+    EnemyDefinitions,
+};
+use crate::ingame::rng::GameRng;
 
-const GAMEOVER_TEXT: &str = "ゲームオーバー";
+const GAMEOVER_TEXT: &str = "gameover.title";
 const GAMEOVER_SIZE: f32 = 20.0;
-const SCORE_TEXT: &str = "スコア: ";
-const FIGHTER_SCALE: Vec3 = Vec3::splat(0.75);
-const TORPEDO_SCALE: Vec3 = Vec3::new(0.5, 0.75, 0.0);
-const RETRY_TEXT: &str = "リトライ: Key[R]";
-const BACKTOTITLE_TEXT: &str = "タイトルに戻る: Key[B]";
+const SCORE_TEXT: &str = "common.score";
+const ICON_SCALE: f32 = 0.75;
+const ICON_MAX_WIDTH: f32 = 64.0;
+const RETRY_TEXT: &str = "gameover.retry";
+const BACKTOTITLE_TEXT: &str = "gameover.back_to_title";
+const HIGHSCORE_TEXT: &str = "gameover.highscore";
+const CONFIRM_TEXT: &str = "gameover.confirm";
+const DEFAULT_NAME: &str = "NONAME";
+const SEED_TEXT: &str = "gameover.seed";
+const SEED_SIZE: f32 = 12.0;
+const NAME_KEYS: [(KeyCode, char); 36] = [
+    (KeyCode::KeyA, 'A'), (KeyCode::KeyB, 'B'), (KeyCode::KeyC, 'C'), (KeyCode::KeyD, 'D'),
+    (KeyCode::KeyE, 'E'), (KeyCode::KeyF, 'F'), (KeyCode::KeyG, 'G'), (KeyCode::KeyH, 'H'),
+    (KeyCode::KeyI, 'I'), (KeyCode::KeyJ, 'J'), (KeyCode::KeyK, 'K'), (KeyCode::KeyL, 'L'),
+    (KeyCode::KeyM, 'M'), (KeyCode::KeyN, 'N'), (KeyCode::KeyO, 'O'), (KeyCode::KeyP, 'P'),
+    (KeyCode::KeyQ, 'Q'), (KeyCode::KeyR, 'R'), (KeyCode::KeyS, 'S'), (KeyCode::KeyT, 'T'),
+    (KeyCode::KeyU, 'U'), (KeyCode::KeyV, 'V'), (KeyCode::KeyW, 'W'), (KeyCode::KeyX, 'X'),
+    (KeyCode::KeyY, 'Y'), (KeyCode::KeyZ, 'Z'), (KeyCode::Digit0, '0'), (KeyCode::Digit1, '1'),
+    (KeyCode::Digit2, '2'), (KeyCode::Digit3, '3'), (KeyCode::Digit4, '4'), (KeyCode::Digit5, '5'),
+    (KeyCode::Digit6, '6'), (KeyCode::Digit7, '7'), (KeyCode::Digit8, '8'), (KeyCode::Digit9, '9'),
+];
 const BOARD_SIZE: Vec2 = Vec2::new(360.0, 270.0);
 const BOARD_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
 const TEXT_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
@@ -26,6 +57,22 @@ const TEXT_PADDING: f32 = 50.0;
 #[require(Text2d, TextFont, TextColor, Transform)]
 struct Gameover;
 
+#[derive(Component)]
+struct MenuText;
+
+#[derive(Component)]
+struct NameEntryText;
+
+#[derive(Component)]
+struct NameText;
+
+#[derive(Resource, Deref, DerefMut, Default)]
+pub struct NameEntry(Option<String>);
+
+// character picked with the d-pad, as an index into NAME_KEYS
+#[derive(Resource, Deref, DerefMut, Default)]
+struct NameCursor(Option<usize>);
+
 impl Gameover {
     fn new(
         text: String,
@@ -69,23 +116,37 @@ fn setup(
     asset_server: Res<AssetServer>,
     camera_query: Query<&Transform, With<MyCamera>>,
     score: Res<Score>,
+    strings: Res<Strings>,
+    definitions: Res<EnemyDefinitions>,
+    enemy_definitions: Res<Assets<EnemyDefinition>>,
+    highscores: Res<HighScores>,
+    rng: Option<Res<GameRng>>,
+    mut name_entry: ResMut<NameEntry>,
+    mut name_cursor: ResMut<NameCursor>,
 ) {
     // debug!("setup");
+    let qualifies = highscores.qualifies(score.sum());
+    let menu_visibility = if qualifies { Visibility::Hidden } else { Visibility::Inherited };
+    **name_entry = qualifies.then(String::new);
+    **name_cursor = None;
     let Ok(camera_transform) = camera_query.get_single() else { return };
     let camera_y = camera_transform.translation.y;
-    let font = asset_server.load(PATH_FONT);
+    let font = strings.font();
     // game over
     let translation = Vec3::new(
         0.0,
         camera_y + TEXT_PADDING * 2.0,
         0.0,
     );
-    commands.spawn(Gameover::new(
-        GAMEOVER_TEXT.to_string(), 
-        font.clone(),
-        GAMEOVER_SIZE, 
-        TEXT_COLOR, 
-        translation,
+    commands.spawn((
+        Gameover::new(
+            strings.get(GAMEOVER_TEXT).to_string(),
+            font.clone(),
+            GAMEOVER_SIZE,
+            TEXT_COLOR,
+            translation,
+        ),
+        Localized(GAMEOVER_TEXT),
     ));
     // score
     let translation = Vec3::new(
@@ -94,80 +155,131 @@ fn setup(
         0.0,
     );
     commands.spawn(Gameover::new(
-        format!("{}{}", SCORE_TEXT, score.sum()), 
-        font.clone(),
-        TEXT_SIZE, 
-        TEXT_COLOR, 
-        translation,
-    ));
-    // fighter image
-    let image = asset_server.load(PATH_IMAGE_FIGHTER);
-    let translation = Vec3::new(
-        -TEXT_PADDING * 1.0,
-        camera_y + TEXT_PADDING * 0.5, 
-        0.0,
-    );
-    commands.spawn(Gameover::from_image(image, translation, FIGHTER_SCALE));
-    // fighter score
-    let translation = Vec3::new(
-        TEXT_PADDING * 0.2,
-        camera_y + TEXT_PADDING * 0.5,
-        0.0,
-    );
-    commands.spawn(Gameover::new(
-        format!(" x {} ({})", score.fighter, score.sum_fighter()), 
-        font.clone(),
-        TEXT_SIZE, 
-        TEXT_COLOR, 
-        translation,
-    ));
-    // torpedo image
-    let image = asset_server.load(PATH_IMAGE_TORPEDO);
-    let translation = Vec3::new(
-        -TEXT_PADDING * 1.0,
-        camera_y - TEXT_PADDING * 0.0,
-        0.0,
-    );
-    commands.spawn(Gameover::from_image(image, translation, TORPEDO_SCALE));
-    // torpedo score
-    let translation = Vec3::new(
-        TEXT_PADDING * 0.2,
-        camera_y - TEXT_PADDING * 0.0,
-        0.0,
-    );
-    commands.spawn(Gameover::new(
-        format!(" x {} ({})", score.torpedo, score.sum_torpedo()), 
+        format!("{}{}", strings.get(SCORE_TEXT), score.sum()),
         font.clone(),
         TEXT_SIZE, 
         TEXT_COLOR, 
         translation,
     ));
+    // enemy kills
+    for (i, (_, definition)) in definitions.iter(&enemy_definitions).enumerate() {
+        let row_y = camera_y + TEXT_PADDING * (0.5 - 0.5 * i as f32);
+        // enemy image
+        let image = asset_server.load(&definition.sprite);
+        let translation = Vec3::new(
+            -TEXT_PADDING * 1.0,
+            row_y,
+            0.0,
+        );
+        let scale = Vec3::new(
+            (ICON_MAX_WIDTH / definition.size.x).min(ICON_SCALE),
+            ICON_SCALE,
+            0.0,
+        );
+        commands.spawn(Gameover::from_image(image, translation, scale));
+        // enemy score
+        let translation = Vec3::new(
+            TEXT_PADDING * 0.2,
+            row_y,
+            0.0,
+        );
+        commands.spawn(Gameover::new(
+            format!(" x {} ({})", score.count(&definition.name), score.sum_of(&definition.name)), 
+            font.clone(),
+            TEXT_SIZE, 
+            TEXT_COLOR, 
+            translation,
+        ));
+    }
     // retry
     let translation = Vec3::new(
         0.0,
         camera_y - TEXT_PADDING * 1.0,
         0.0,
     );
-    commands.spawn(Gameover::new(
-        RETRY_TEXT.to_string(), 
-        font.clone(),
-        TEXT_SIZE, 
-        TEXT_COLOR, 
-        translation,
+    commands.spawn((
+        Gameover::new(
+            strings.get(RETRY_TEXT).to_string(),
+            font.clone(),
+            TEXT_SIZE,
+            TEXT_COLOR,
+            translation,
+        ),
+        Localized(RETRY_TEXT),
+        MenuText,
+        menu_visibility,
     ));
+    // highscore name
+    if qualifies {
+        commands.spawn((
+            Gameover::new(
+                strings.get(HIGHSCORE_TEXT).to_string(),
+                font.clone(),
+                TEXT_SIZE,
+                TEXT_COLOR,
+                translation,
+            ),
+            Localized(HIGHSCORE_TEXT),
+            NameEntryText,
+        ))
+        .with_child((
+            TextSpan::default(),
+            TextFont {
+                font: font.clone(),
+                font_size: TEXT_SIZE,
+                ..Default::default()
+            },
+            TextColor(TEXT_COLOR),
+            NameText,
+        ));
+    }
     // back to title
     let translation = Vec3::new(
         0.0,
         camera_y - TEXT_PADDING * 2.0,
         0.0,
     );
-    commands.spawn(Gameover::new(
-        BACKTOTITLE_TEXT.to_string(), 
-        font.clone(),
-        TEXT_SIZE, 
-        TEXT_COLOR, 
-        translation,
+    commands.spawn((
+        Gameover::new(
+            strings.get(BACKTOTITLE_TEXT).to_string(),
+            font.clone(),
+            TEXT_SIZE,
+            TEXT_COLOR,
+            translation,
+        ),
+        Localized(BACKTOTITLE_TEXT),
+        MenuText,
+        menu_visibility,
     ));
+    // seed
+    if let Some(rng) = rng {
+        let translation = Vec3::new(
+            0.0,
+            camera_y - TEXT_PADDING * 2.5,
+            0.0,
+        );
+        commands.spawn(Gameover::new(
+            format!("{}{}", strings.get(SEED_TEXT), rng.seed()),
+            font.clone(),
+            SEED_SIZE,
+            TEXT_COLOR,
+            translation,
+        ));
+    }
+    // confirm name
+    if qualifies {
+        commands.spawn((
+            Gameover::new(
+                strings.get(CONFIRM_TEXT).to_string(),
+                font.clone(),
+                TEXT_SIZE,
+                TEXT_COLOR,
+                translation,
+            ),
+            Localized(CONFIRM_TEXT),
+            NameEntryText,
+        ));
+    }
     // board
     let (x, y, z) = (
         0.0,
@@ -185,22 +297,89 @@ fn setup(
     ));
 }
 
+fn enter_name(
+    mut name_entry: ResMut<NameEntry>,
+    mut name_cursor: ResMut<NameCursor>,
+    mut highscores: ResMut<HighScores>,
+    mut entry_query: Query<&mut Visibility, (With<NameEntryText>, Without<MenuText>)>,
+    mut menu_query: Query<&mut Visibility, (With<MenuText>, Without<NameEntryText>)>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    score: Res<Score>,
+) {
+    let Some(name) = name_entry.0.as_mut() else { return };
+    let mut confirmed = false;
+
+    for gamepad in &gamepads {
+        let cursor = name_cursor.unwrap_or(0);
+
+        if gamepad.just_pressed(GamepadButton::DPadUp) {
+            **name_cursor = Some((cursor + NAME_KEYS.len() - 1) % NAME_KEYS.len());
+        }
+        if gamepad.just_pressed(GamepadButton::DPadDown) {
+            **name_cursor = Some((cursor + 1) % NAME_KEYS.len());
+        }
+        // the letter under the cursor after this frame's move
+        if gamepad.just_pressed(GamepadButton::South) && name.len() < NAME_LENGTH {
+            name.push(NAME_KEYS[name_cursor.unwrap_or(0)].1);
+        }
+        if gamepad.just_pressed(GamepadButton::East) { name.pop(); }
+        if gamepad.just_pressed(GamepadButton::Start) { confirmed = true }
+    }
+
+    for key in keyboard_input.get_just_pressed() {
+        match key {
+            KeyCode::Backspace => { name.pop(); }
+            KeyCode::Enter => confirmed = true,
+            key => {
+                let Some((_, c)) = NAME_KEYS.iter().find(|(code, _)| code == key) else { continue };
+                if name.len() < NAME_LENGTH { name.push(*c) }
+            }
+        }
+    }
+    if !confirmed { return }
+
+    let name = name_entry.0.take()
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| DEFAULT_NAME.to_string());
+    // debug!("enter_name: {}", name);
+    highscores.insert(HighScore {
+        name,
+        score: score.sum(),
+        kills: score.kills().to_vec(),
+    });
+    for mut visibility in &mut entry_query { *visibility = Visibility::Hidden }
+    for mut visibility in &mut menu_query { *visibility = Visibility::Inherited }
+}
+
+fn update_name(
+    name_entry: Res<NameEntry>,
+    name_cursor: Res<NameCursor>,
+    mut query: Query<&mut TextSpan, With<NameText>>,
+) {
+    if !name_entry.is_changed() && !name_cursor.is_changed() { return }
+    let Some(name) = name_entry.0.as_ref() else { return };
+    let Ok(mut span) = query.get_single_mut() else { return };
+    let cursor = name_cursor.map_or('_', |cursor| NAME_KEYS[cursor].1);
+
+    **span = format!("{}{}", name, cursor);
+}
+
 fn update(
     mut next_state: ResMut<NextState<AppState>>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    actions: Actions,
+    name_entry: Res<NameEntry>,
 ) {
+    // wait for the highscore name
+    if name_entry.is_some() { return }
+
     let mut closure = |app_state: AppState| {
         // trace!("AppState Gameover -> {:?}", app_state);
         next_state.set(app_state);
     };
 
-    for key in keyboard_input.get_just_pressed() {
-        match key {
-            KeyCode::KeyR => closure(AppState::Ingame),
-            KeyCode::KeyB => closure(AppState::Mainmenu),
-            _ => {},
-        }
-    }
+    if actions.just_pressed(InputAction::Retry) { closure(AppState::Restart) }
+    if actions.just_pressed(InputAction::BackToTitle) { closure(AppState::Mainmenu) }
 }
 
 fn all_despawn(
@@ -208,7 +387,7 @@ fn all_despawn(
     query: Query<Entity, With<Gameover>>,
 ) {
     // debug!("all_despawn");
-    for entity in &query { commands.entity(entity).despawn() }
+    for entity in &query { commands.entity(entity).despawn_recursive() }
 }
 
 pub struct GameoverPlugin;
@@ -216,8 +395,14 @@ pub struct GameoverPlugin;
 impl Plugin for GameoverPlugin {
     fn build(&self, app: &mut App) {
         app
+            .init_resource::<NameEntry>()
+            .init_resource::<NameCursor>()
             .add_systems(OnEnter(AppState::Gameover), setup)
-            .add_systems(Update, update.run_if(in_state(AppState::Gameover)))
+            .add_systems(Update, (
+                enter_name,
+                update_name,
+                update,
+            ).chain().run_if(in_state(AppState::Gameover)))
             .add_systems(OnExit(AppState::Gameover), all_despawn)
         ;
     }
@@ -0,0 +1,256 @@
+use bevy::{
+    prelude::*,
+    asset::AssetMetaCheck,
+    audio::AudioSource,
+    input::{
+        ButtonState,
+        InputPlugin,
+        gamepad::{
+            GamepadConnection,
+            GamepadConnectionEvent,
+            RawGamepadAxisChangedEvent,
+            RawGamepadButtonChangedEvent,
+            RawGamepadEvent,
+        },
+        keyboard::{Key, KeyboardInput, NativeKey},
+    },
+    state::app::StatesPlugin,
+    time::TimeUpdateStrategy,
+};
+use std::{
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    AppState,
+    GamePlugin,
+    Score,
+};
+use crate::ingame::{
+    IngameState,
+    Pooled,
+};
+use crate::ingame::enemy::Enemy;
+use crate::ingame::player::Player;
+use crate::ingame::stage::CurrentStage;
+use crate::loading::LoadingProgress;
+use crate::replay::{
+    Replay,
+    ReplayState,
+};
+
+const TIMESTEP: f64 = 1.0 / 60.0;
+const LOAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationReport {
+    pub ticks: u32,
+    pub state: AppState,
+    pub paused: bool,
+    pub score: usize,
+    pub stage: usize,
+    pub fighter_kills: usize,
+    pub torpedo_kills: usize,
+    pub enemies: usize,
+    pub atlas_layouts: usize,
+    pub pooled: usize,
+    pub player: Option<PlayerReport>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerReport {
+    pub hp: usize,
+    pub bullets: usize,
+    pub weapon: usize,
+    pub bombs: usize,
+    pub position: Vec2,
+}
+
+pub struct HeadlessApp {
+    app: App,
+    ticks: u32,
+}
+
+impl HeadlessApp {
+    pub fn new() -> Self {
+        Self::start(Self::game())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        Self::start(Self::game().seed(seed))
+    }
+
+    pub fn playback(replay: Replay) -> Self {
+        Self::start(Self::game().replay(replay))
+    }
+
+    fn game() -> GamePlugin {
+        GamePlugin::new()
+            .initial_state(AppState::Mainmenu)
+            .persistent(false)
+            .background(false)
+            .mainmenu(false)
+    }
+
+    fn start(game: GamePlugin) -> Self {
+        let mut headless = Self::with_plugin(game);
+        headless.wait_for_assets();
+        headless.app.world_mut().resource_mut::<NextState<AppState>>().set(AppState::Ingame);
+        headless
+    }
+
+    pub fn with_plugin(game: GamePlugin) -> Self {
+        let mut app = App::new();
+
+        app
+            .add_plugins(MinimalPlugins)
+            .add_plugins(AssetPlugin {
+                meta_check: AssetMetaCheck::Never,
+                ..Default::default()
+            })
+            .add_plugins(StatesPlugin)
+            .add_plugins(InputPlugin)
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .init_asset::<Font>()
+            .init_asset::<AudioSource>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(TIMESTEP)))
+            .add_plugins(game)
+        ;
+        app.finish();
+        app.cleanup();
+
+        Self { app, ticks: 0 }
+    }
+
+    pub fn app(&self) -> &App { &self.app }
+
+    pub fn app_mut(&mut self) -> &mut App { &mut self.app }
+
+    pub fn replay(&self) -> &Replay { self.app.world().resource::<ReplayState>().replay() }
+
+    pub fn wait_for_assets(&mut self) {
+        let mut waited = Duration::ZERO;
+
+        loop {
+            self.app.update();
+            let world = self.app.world();
+            let missing = &world.resource::<LoadingProgress>().missing;
+
+            if !missing.is_empty() { panic!("failed to load assets: {}", missing.join(", ")) }
+            if *world.resource::<State<AppState>>().get() != AppState::Loading { return }
+            if waited >= LOAD_TIMEOUT { panic!("timed out waiting for assets to load") }
+
+            thread::sleep(Duration::from_millis(1));
+            waited += Duration::from_millis(1);
+        }
+    }
+
+    pub fn set_frame_rate(&mut self, fps: u32) {
+        let frame = Duration::from_secs_f64(1.0 / fps as f64);
+        self.app.insert_resource(TimeUpdateStrategy::ManualDuration(frame));
+    }
+
+    pub fn press(&mut self, key_code: KeyCode) {
+        self.send_key(key_code, ButtonState::Pressed);
+    }
+
+    pub fn release(&mut self, key_code: KeyCode) {
+        self.send_key(key_code, ButtonState::Released);
+    }
+
+    pub fn connect_gamepad(&mut self) -> Entity {
+        let world = self.app.world_mut();
+        let gamepad = world.spawn_empty().id();
+
+        world.send_event(GamepadConnectionEvent::new(gamepad, GamepadConnection::Connected {
+            name: "headless".to_string(),
+            vendor_id: None,
+            product_id: None,
+        }));
+        gamepad
+    }
+
+    pub fn press_button(&mut self, gamepad: Entity, button: GamepadButton) {
+        self.send_button(gamepad, button, 1.0);
+    }
+
+    pub fn release_button(&mut self, gamepad: Entity, button: GamepadButton) {
+        self.send_button(gamepad, button, 0.0);
+    }
+
+    pub fn move_stick(&mut self, gamepad: Entity, stick: Vec2) {
+        let world = self.app.world_mut();
+
+        world.send_event(RawGamepadEvent::Axis(RawGamepadAxisChangedEvent::new(gamepad, GamepadAxis::LeftStickX, stick.x)));
+        world.send_event(RawGamepadEvent::Axis(RawGamepadAxisChangedEvent::new(gamepad, GamepadAxis::LeftStickY, stick.y)));
+    }
+
+    pub fn step(&mut self, ticks: u32) -> SimulationReport {
+        for _ in 0..ticks {
+            self.app.update();
+            self.ticks += 1;
+        }
+        self.report()
+    }
+
+    pub fn report(&mut self) -> SimulationReport {
+        let world = self.app.world_mut();
+        let state = world.resource::<State<AppState>>().get().clone();
+        let paused = world.get_resource::<State<IngameState>>()
+            .is_some_and(|state| *state.get() == IngameState::Paused);
+        let score = world.resource::<Score>();
+        let (score, fighter_kills, torpedo_kills) = (
+            score.sum(),
+            score.count("fighter"),
+            score.count("torpedo"),
+        );
+        let stage = **world.resource::<CurrentStage>();
+        let enemies = world.query_filtered::<(), With<Enemy>>().iter(world).count();
+        let atlas_layouts = world.resource::<Assets<TextureAtlasLayout>>().len();
+        let pooled = world.query_filtered::<(), With<Pooled>>().iter(world).count();
+        let player = world.query::<(&Player, &Transform)>()
+            .get_single(world)
+            .ok()
+            .map(|(player, transform)| PlayerReport {
+                hp: player.hp,
+                bullets: player.bullets,
+                weapon: player.weapon,
+                bombs: player.bombs,
+                position: transform.translation.xy(),
+            });
+
+        SimulationReport {
+            ticks: self.ticks,
+            state,
+            paused,
+            score,
+            stage,
+            fighter_kills,
+            torpedo_kills,
+            enemies,
+            atlas_layouts,
+            pooled,
+            player,
+        }
+    }
+
+    fn send_button(&mut self, gamepad: Entity, button: GamepadButton, value: f32) {
+        self.app.world_mut().send_event(RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(gamepad, button, value)));
+    }
+
+    fn send_key(&mut self, key_code: KeyCode, state: ButtonState) {
+        self.app.world_mut().send_event(KeyboardInput {
+            key_code,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            state,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+    }
+}
+
+impl Default for HeadlessApp {
+    fn default() -> Self { Self::new() }
+}
@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::{
+    GameConfig,
+    Kills,
+    storage,
+};
+
+const FILE_NAME: &str = "highscores.ron";
+pub const MAX_ENTRIES: usize = 10;
+pub const NAME_LENGTH: usize = 8;
+
+#[derive(Resource, Serialize, Deserialize, Default, Debug, Clone)]
+pub struct HighScores {
+    entries: Vec<HighScore>,
+    #[serde(skip)]
+    persistent: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HighScore {
+    pub name: String,
+    pub score: usize,
+    pub kills: Vec<Kills>,
+}
+
+impl HighScores {
+    fn load(persistent: bool) -> Self {
+        let mut highscores = persistent
+            .then(|| storage::load(FILE_NAME))
+            .flatten()
+            .and_then(|contents| match ron::from_str::<Self>(&contents) {
+                Ok(highscores) => Some(highscores),
+                Err(err) => {
+                    warn!("failed to read highscores: {}", err);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        highscores.persistent = persistent;
+        highscores
+    }
+
+    pub fn entries(&self) -> &[HighScore] { &self.entries }
+
+    pub fn qualifies(&self, score: usize) -> bool {
+        score > 0 && (
+            self.entries.len() < MAX_ENTRIES ||
+            self.entries.last().is_some_and(|entry| score > entry.score)
+        )
+    }
+
+    pub fn insert(&mut self, entry: HighScore) -> Option<usize> {
+        let rank = self.entries.iter()
+            .position(|other| entry.score > other.score)
+            .unwrap_or(self.entries.len());
+
+        if rank >= MAX_ENTRIES { return None }
+
+        self.entries.insert(rank, entry);
+        self.entries.truncate(MAX_ENTRIES);
+        self.save();
+        Some(rank)
+    }
+
+    fn save(&self) {
+        if !self.persistent { return }
+
+        let result = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())
+            .and_then(|contents| storage::save(FILE_NAME, &contents).map_err(|err| err.to_string()));
+
+        if let Err(err) = result { warn!("failed to save highscores: {}", err) }
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+) {
+    // debug!("setup");
+    commands.insert_resource(HighScores::load(config.persistent));
+}
+
+pub struct HighscorePlugin;
+
+impl Plugin for HighscorePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(PreStartup, setup)
+        ;
+    }
+}
@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+
+use crate::ingame::TickSet;
+use crate::ingame::camera::ScrollLock;
+use crate::ingame::rng::GameRng;
+use crate::ingame::enemy::{
+    EnemyDefinition,
+    EmitterDefinition,
+    BossPhase,
+    BulletEmitter,
+    Health,
+};
+
+#[derive(Component)]
+pub struct Boss {
+    pub max_hp: usize,
+    pub phases: Vec<BossPhase>,
+    pub phase: usize,
+    emitter: EmitterDefinition,
+}
+
+impl Boss {
+    pub fn new(definition: &EnemyDefinition) -> Self {
+        Self {
+            max_hp: definition.hp,
+            phases: definition.phases.clone(),
+            phase: 0,
+            emitter: definition.emitter.clone(),
+        }
+    }
+
+    fn phase_for(&self, hp: usize) -> usize {
+        let ratio = hp as f32 / self.max_hp as f32;
+
+        self.phases.iter().filter(|phase| ratio <= phase.threshold).count()
+    }
+
+    fn emitter(&self) -> &EmitterDefinition {
+        match self.phase {
+            0 => &self.emitter,
+            phase => &self.phases[phase - 1].emitter,
+        }
+    }
+}
+
+fn change_phase(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Boss, &Health), Changed<Health>>,
+    mut rng: ResMut<GameRng>,
+) {
+    for (entity, mut boss, health) in &mut query {
+        let phase = boss.phase_for(**health);
+
+        if phase == boss.phase { continue }
+        // debug!("change_phase: {}", phase);
+        boss.phase = phase;
+        let emitter = boss.emitter();
+        let interval = emitter.interval(&mut *rng);
+        commands.entity(entity).insert(BulletEmitter::new(emitter, interval));
+    }
+}
+
+fn update_scroll_lock(
+    mut lock: ResMut<ScrollLock>,
+    query: Query<(), With<Boss>>,
+) {
+    let locked = !query.is_empty();
+
+    if **lock == locked { return }
+    // debug!("update_scroll_lock: {}", locked);
+    **lock = locked;
+}
+
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(FixedUpdate, (
+                change_phase,
+                update_scroll_lock,
+            ).chain().in_set(TickSet::Enemy))
+        ;
+    }
+}
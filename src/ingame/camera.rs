@@ -1,21 +1,84 @@
 use bevy::prelude::*;
+use rand::Rng;
 
 use crate::{
     AppState,
     MyCamera,
 };
+use crate::settings::Settings;
+use crate::ingame::TickSet;
+use crate::ingame::player::PlayerDamagedEvent;
 
-pub const SPEED: f32 = 0.2;
+pub const SPEED: f32 = 12.0;
+const SHAKE_SECONDS: f32 = 0.3;
+const SHAKE_STRENGTH: f32 = 6.0;
+
+#[derive(Resource, Deref, DerefMut, Default)]
+pub struct ScrollLock(pub bool);
+
+#[derive(Resource, Deref, DerefMut)]
+struct CameraShake(Timer);
+
+#[derive(Resource, Deref, DerefMut, Default)]
+struct ShakeOffset(Vec3);
+
+impl ScrollLock {
+    pub fn speed(&self) -> f32 {
+        if **self { 0.0 } else { SPEED }
+    }
+}
 
 fn scrollup(
     mut query: Query<&mut Transform, With<MyCamera>>,
+    scroll: Res<ScrollLock>,
+    time: Res<Time>,
 ) {
     let Ok(mut transform) = query.get_single_mut() else { return };
 
-    transform.translation.y += SPEED;
+    transform.translation.y += scroll.speed() * time.delta_secs();
     // trace!("camera y: {}", transform.translation.y);
 }
 
+fn start_shake(
+    mut events: EventReader<PlayerDamagedEvent>,
+    mut shake: ResMut<CameraShake>,
+) {
+    if events.is_empty() { return }
+    events.clear();
+
+    // debug!("start_shake");
+    shake.reset();
+}
+
+// the shake only moves the rendered camera, so it stays out of the simulation and replays
+fn shake(
+    mut query: Query<&mut Transform, With<MyCamera>>,
+    mut shake: ResMut<CameraShake>,
+    mut offset: ResMut<ShakeOffset>,
+    settings: Res<Settings>,
+    time: Res<Time<Virtual>>,
+) {
+    if shake.finished() || time.is_paused() { return }
+    let Ok(mut transform) = query.get_single_mut() else { return };
+
+    shake.tick(time.delta());
+    if !settings.screen_shake { return }
+    let strength = SHAKE_STRENGTH * shake.fraction_remaining();
+    **offset = Vec3::X * rand::thread_rng().gen_range(-1.0..=1.0) * strength;
+    transform.translation += **offset;
+}
+
+fn unshake(
+    mut query: Query<&mut Transform, With<MyCamera>>,
+    mut offset: ResMut<ShakeOffset>,
+) {
+    if **offset == Vec3::ZERO { return }
+    let Ok(mut transform) = query.get_single_mut() else { return };
+
+    transform.translation -= **offset;
+    **offset = Vec3::ZERO;
+}
+
 fn reset_position(
     mut query: Query<&mut Transform, With<MyCamera>>,
 ) {
@@ -25,13 +88,37 @@ fn reset_position(
     transform.translation = Vec3::ZERO;
 }
 
+fn unlock(
+    mut scroll: ResMut<ScrollLock>,
+) {
+    // debug!("unlock");
+    **scroll = false;
+}
+
+fn finished_timer(seconds: f32) -> Timer {
+    let mut timer = Timer::from_seconds(seconds, TimerMode::Once);
+    timer.tick(timer.duration());
+    timer
+}
+
 pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(Update, scrollup.run_if(in_state(AppState::Ingame)))
-            .add_systems(OnExit(AppState::Gameover), reset_position)
+            .init_resource::<ScrollLock>()
+            .insert_resource(CameraShake(finished_timer(SHAKE_SECONDS)))
+            .init_resource::<ShakeOffset>()
+            .add_systems(OnExit(AppState::Ingame), unlock)
+            .add_systems(FixedUpdate, (
+                scrollup,
+                start_shake,
+            ).chain().in_set(TickSet::Camera))
+            .add_systems(First, unshake)
+            .add_systems(PostUpdate, shake.before(TransformSystem::TransformPropagate))
+            .add_systems(OnExit(AppState::StageClear), reset_position)
+            .add_systems(OnEnter(AppState::Mainmenu), reset_position)
+            .add_systems(OnEnter(AppState::Restart), reset_position)
         ;
     }
 }
@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::atlas::AtlasLayouts;
+use crate::loading::GameAssets;
+use crate::ingame::GRID_SIZE;
+use crate::ingame::enemy::BulletType;
+use crate::ingame::utils::prelude::*;
+
+const IMAGE_SIZE_FIGHTER: UVec2 = UVec2::new(4, 16);
+const COLUMN_FIGHTER: u32 = 4;
+const SCALE_FIGHTER: Vec3 = Vec3::splat(2.0);
+// hitboxes are in the image's own pixels and follow the bullet's rotation and scale
+const HITBOX_FIGHTER: Hitbox = Hitbox::capsule(2.0, Vec2::new(0.0, 6.0));
+const IMAGE_SIZE_TORPEDO: UVec2 = UVec2::new(11, 32);
+const COLUMN_TORPEDO: u32 = 3;
+const SCALE_TORPEDO: Vec3 = Vec3::splat(1.5);
+const HITBOX_TORPEDO: Hitbox = Hitbox::capsule(4.0, Vec2::new(0.0, 11.0));
+const ROW: u32 = 1;
+pub const SPEED: f32 = 256.0;
+const FPS: f32 = 0.1;
+
+#[derive(Resource)]
+pub struct BulletImages {
+    fighter: Handle<Image>,
+    fighter_layout: Handle<TextureAtlasLayout>,
+    torpedo: Handle<Image>,
+    torpedo_layout: Handle<TextureAtlasLayout>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut atlas_layouts: ResMut<AtlasLayouts>,
+    game_assets: Res<GameAssets>,
+) {
+    // debug!("setup");
+    commands.insert_resource(BulletImages {
+        fighter: game_assets.fighter_bullet.clone(),
+        fighter_layout: atlas_layouts.grid(&mut texture_atlas_layouts, IMAGE_SIZE_FIGHTER, COLUMN_FIGHTER, ROW),
+        torpedo: game_assets.torpedo_bullet.clone(),
+        torpedo_layout: atlas_layouts.grid(&mut texture_atlas_layouts, IMAGE_SIZE_TORPEDO, COLUMN_TORPEDO, ROW),
+    });
+}
+
+pub fn muzzle(position: Vec2) -> Vec2 {
+    Vec2::new(position.x, position.y - GRID_SIZE * 2.0)
+}
+
+pub fn spawn_bullet(
+    commands: &mut Commands,
+    pool: &mut Pool,
+    images: &BulletImages,
+    bullet_type: BulletType,
+    origin: Vec2,
+    direction: Vec2,
+) -> Entity {
+    let translation = origin.extend(99.0);
+    let degrees = direction.y.atan2(direction.x).to_degrees() - 90.0;
+    let (image, layout, column, hitbox, scale) = match bullet_type {
+        BulletType::Fighter => (
+            images.fighter.clone(),
+            images.fighter_layout.clone(),
+            COLUMN_FIGHTER,
+            HITBOX_FIGHTER,
+            SCALE_FIGHTER,
+        ),
+        BulletType::Torpedo => (
+            images.torpedo.clone(),
+            images.torpedo_layout.clone(),
+            COLUMN_TORPEDO,
+            HITBOX_TORPEDO,
+            SCALE_TORPEDO,
+        ),
+    };
+
+    let animation_config = AnimationConfig::new(AnimationName::Bullet, 0, column as usize - 1, FPS);
+    let velocity = Velocity(direction * SPEED);
+    let bullet = Bullet::new(
+        CollisionLayers::ENEMY_SHOT,
+        hitbox,
+        image,
+        layout,
+        animation_config.first_sprite_index,
+        translation,
+        degrees,
+        scale,
+    );
+    // debug!("shoot");
+    pool.spawn(commands, Pooled::Bullet, (bullet, animation_config, velocity))
+}
+
+pub struct BulletPlugin;
+
+impl Plugin for BulletPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(AppState::Ingame), setup)
+        ;
+    }
+}
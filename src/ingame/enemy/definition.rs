@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+use std::ops::Range;
+
+use crate::ron_asset::RonAsset;
+use crate::ingame::utils::prelude::*;
+
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct EnemyDefinition {
+    pub name: String,
+    pub sprite: String,
+    pub size: Vec2,
+    // defaults to a box the size of the sprite
+    #[serde(default)]
+    pub hitbox: Option<Hitbox>,
+    pub hp: usize,
+    pub speed: f32,
+    pub direction: Vec2,
+    pub movement: MovementPattern,
+    pub emitter: EmitterDefinition,
+    pub explosion: String,
+    pub explosion_frames: u32,
+    pub score: usize,
+    #[serde(default)]
+    pub drop_rate: f32,
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+    #[serde(default)]
+    pub phases: Vec<BossPhase>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum MovementPattern {
+    Straight,
+    Bounce,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum BulletType {
+    Fighter,
+    Torpedo,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct EmitterDefinition {
+    pub pattern: BulletPattern,
+    pub bullet: BulletType,
+    pub interval: Range<f32>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum BulletPattern {
+    Single,
+    Aimed,
+    Spread { count: usize, angle: f32, #[serde(default)] aimed: bool },
+    Ring { count: usize },
+    Spiral { count: usize, step: f32 },
+    AimedBurst { count: usize, delay: f32 },
+    Wave { amplitude: f32, frequency: f32, #[serde(default)] aimed: bool },
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct BossPhase {
+    pub threshold: f32,
+    pub emitter: EmitterDefinition,
+}
+
+impl EnemyDefinition {
+    pub fn validate(&self) -> Result<(), String> {
+        // the explosion animates from the first frame to the last
+        if self.explosion_frames == 0 { return Err(format!("{}: explosion_frames must be at least 1", self.name)) }
+        Ok(())
+    }
+}
+
+impl EmitterDefinition {
+    pub fn interval(&self, rng: &mut impl Rng) -> f32 {
+        if self.interval.is_empty() { return self.interval.start }
+        rng.gen_range(self.interval.clone())
+    }
+}
+
+fn default_scale() -> f32 { 1.0 }
+
+impl RonAsset for EnemyDefinition {
+    const EXTENSIONS: &'static [&'static str] = &["enemy.ron"];
+
+    fn validate(&self) -> Result<(), String> { EnemyDefinition::validate(self) }
+}
@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+
+use crate::atlas::AtlasLayouts;
+use crate::ingame::IngameState;
+use crate::ingame::enemy::{
+    EnemyDefinition,
+    EnemyKilledEvent,
+};
+use crate::ingame::utils::prelude::*;
+
+const IMAGE_SIZE: UVec2 = UVec2::splat(64);
+const ROW: u32 = 1;
+const FPS: f32 = 0.1;
+
+// explosion layouts are made as the definitions load, not on the first kill
+fn prefetch(
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut atlas_layouts: ResMut<AtlasLayouts>,
+    mut events: EventReader<AssetEvent<EnemyDefinition>>,
+    enemy_definitions: Res<Assets<EnemyDefinition>>,
+) {
+    for event in events.read() {
+        let (AssetEvent::Added { id } | AssetEvent::Modified { id }) = *event else { continue };
+        let Some(definition) = enemy_definitions.get(id) else { continue };
+        // debug!("prefetch");
+        atlas_layouts.grid(&mut texture_atlas_layouts, IMAGE_SIZE, definition.explosion_frames, ROW);
+    }
+}
+
+fn spawn(
+    mut commands: Commands,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut atlas_layouts: ResMut<AtlasLayouts>,
+    mut pool: ResMut<Pool>,
+    mut events: EventReader<EnemyKilledEvent>,
+    asset_server: Res<AssetServer>,
+    enemy_definitions: Res<Assets<EnemyDefinition>>,
+) {
+    for event in events.read() {
+        let Some(definition) = enemy_definitions.get(&event.definition) else { continue };
+        let texture_atlas_layout = atlas_layouts.grid(&mut texture_atlas_layouts, IMAGE_SIZE, definition.explosion_frames, ROW);
+        let translation = Vec3::new(
+            event.position.x,
+            event.position.y,
+            99.0,
+        );
+
+        let last_index = definition.explosion_frames as usize - 1;
+        let animation_config = AnimationConfig::new(AnimationName::Despawn, 0, last_index, FPS);
+        // debug!("spawn");
+        pool.spawn(&mut commands, Pooled::Explosion, (
+            Sprite::from_atlas_image(
+                asset_server.load(&definition.explosion),
+                TextureAtlas {
+                    layout: texture_atlas_layout,
+                    index: animation_config.first_sprite_index,
+                },
+            ),
+            Transform::from_translation(translation).with_scale(Vec3::splat(definition.scale)),
+            animation_config,
+        ));
+    }
+}
+
+pub struct DespawnPlugin;
+
+impl Plugin for DespawnPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, prefetch)
+            .add_systems(Update, spawn.run_if(in_state(IngameState::Playing)))
+        ;
+    }
+}
@@ -0,0 +1,157 @@
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+use crate::settings::Difficulty;
+use crate::ingame::TickSet;
+use crate::ingame::enemy::{
+    BulletPattern,
+    BulletType,
+    EmitterDefinition,
+};
+use crate::ingame::enemy::bullet::{
+    SPEED as BULLET_SPEED,
+    BulletImages,
+    muzzle,
+    spawn_bullet,
+};
+use crate::ingame::player::Player;
+use crate::ingame::utils::prelude::*;
+
+const DOWN: Vec2 = Vec2::new(0.0, -1.0);
+
+#[derive(Component)]
+pub struct BulletEmitter {
+    pattern: BulletPattern,
+    bullet: BulletType,
+    timer: Timer,
+    burst_timer: Timer,
+    burst: usize,
+    angle: f32,
+}
+
+#[derive(Component)]
+pub struct WaveMotion {
+    direction: Vec2,
+    amplitude: f32,
+    frequency: f32,
+    elapsed: f32,
+}
+
+impl BulletEmitter {
+    pub fn new(definition: &EmitterDefinition, interval: f32) -> Self {
+        Self {
+            pattern: definition.pattern,
+            bullet: definition.bullet,
+            timer: Timer::from_seconds(interval, TimerMode::Repeating),
+            burst_timer: Timer::from_seconds(0.0, TimerMode::Repeating),
+            burst: 0,
+            angle: 0.0,
+        }
+    }
+
+    pub fn directions(&mut self, aim: Vec2) -> Vec<Vec2> {
+        match self.pattern {
+            BulletPattern::Single => vec![DOWN],
+            BulletPattern::Aimed => vec![aim],
+            BulletPattern::Spread { count, angle, aimed } => {
+                let center = if aimed { aim } else { DOWN };
+                let step = if count > 1 { angle / (count - 1) as f32 } else { 0.0 };
+                let start = if count > 1 { -angle / 2.0 } else { 0.0 };
+
+                (0..count)
+                    .map(|i| Vec2::from_angle((start + step * i as f32).to_radians()).rotate(center))
+                    .collect()
+            }
+            BulletPattern::Ring { count } => ring(count, 0.0),
+            BulletPattern::Spiral { count, step } => {
+                let directions = ring(count, self.angle);
+                self.angle = (self.angle + step) % 360.0;
+                directions
+            }
+            BulletPattern::AimedBurst { count, delay } => {
+                // the burst itself is fired by the burst timer
+                self.burst = count;
+                self.burst_timer = Timer::from_seconds(delay, TimerMode::Repeating);
+                Vec::new()
+            }
+            BulletPattern::Wave { aimed, .. } => vec![if aimed { aim } else { DOWN }],
+        }
+    }
+}
+
+impl WaveMotion {
+    fn new(direction: Vec2, amplitude: f32, frequency: f32) -> Self {
+        Self { direction, amplitude, frequency, elapsed: 0.0 }
+    }
+}
+
+fn ring(count: usize, offset: f32) -> Vec<Vec2> {
+    (0..count)
+        .map(|i| Vec2::from_angle((offset + 360.0 / count as f32 * i as f32).to_radians()))
+        .collect()
+}
+
+fn emit(
+    mut commands: Commands,
+    mut pool: ResMut<Pool>,
+    mut emitter_query: Query<(&mut BulletEmitter, &Transform), Without<Player>>,
+    player_query: Query<&Transform, (With<Player>, Without<BulletEmitter>)>,
+    bullet_images: Res<BulletImages>,
+    difficulty: Res<Difficulty>,
+    time: Res<Time>,
+) {
+    let delta = time.delta().mul_f32(difficulty.fire_rate());
+    let target = player_query.get_single().ok().map(|transform| transform.translation.xy());
+
+    for (mut emitter, emitter_transform) in &mut emitter_query {
+        let origin = muzzle(emitter_transform.translation.xy());
+        let aim = target.map_or(DOWN, |target| (target - origin).normalize_or(DOWN));
+        let mut directions = Vec::new();
+
+        if emitter.timer.tick(delta).just_finished() {
+            directions = emitter.directions(aim);
+        }
+        if emitter.burst > 0 && emitter.burst_timer.tick(delta).just_finished() {
+            emitter.burst -= 1;
+            directions.push(aim);
+        }
+
+        for direction in directions {
+            let bullet = spawn_bullet(
+                &mut commands,
+                &mut pool,
+                &bullet_images,
+                emitter.bullet,
+                origin,
+                direction,
+            );
+            if let BulletPattern::Wave { amplitude, frequency, .. } = emitter.pattern {
+                commands.entity(bullet).insert(WaveMotion::new(direction, amplitude, frequency));
+            }
+        }
+    }
+}
+
+fn wave_motion(
+    mut query: Query<(&mut Velocity, &mut WaveMotion), With<WaveMotion>>,
+    time: Res<Time>,
+) {
+    for (mut velocity, mut wave) in &mut query {
+        wave.elapsed += time.delta_secs();
+        let side = wave.direction.perp() * wave.amplitude * (wave.elapsed * wave.frequency * TAU).sin();
+        **velocity = wave.direction * BULLET_SPEED + side;
+    }
+}
+
+pub struct EmitterPlugin;
+
+impl Plugin for EmitterPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(FixedUpdate, (
+                emit,
+                wave_motion,
+            ).chain().in_set(TickSet::Enemy))
+        ;
+    }
+}
@@ -0,0 +1,144 @@
+use bevy::{
+    prelude::*,
+    asset::UntypedAssetId,
+};
+
+use crate::ron_asset::RonAssetLoader;
+use crate::ingame::utils::prelude::*;
+
+pub mod bullet;
+mod definition;
+mod despawn;
+pub mod emitter;
+mod ship;
+mod sound;
+
+pub use definition::{
+    EnemyDefinition,
+    MovementPattern,
+    BulletType,
+    BulletPattern,
+    EmitterDefinition,
+    BossPhase,
+};
+pub use emitter::BulletEmitter;
+
+const PATHS: [&str; 3] = [
+    "enemies/fighter.enemy.ron",
+    "enemies/torpedo.enemy.ron",
+    "enemies/boss.enemy.ron",
+];
+const DEGREES: f32 = 180.0;
+
+#[derive(Resource)]
+pub struct EnemyDefinitions(Vec<Handle<EnemyDefinition>>);
+
+#[derive(Event)]
+pub struct EnemyDamageEvent(pub Entity);
+
+#[derive(Event)]
+pub struct EnemyKilledEvent {
+    pub name: String,
+    pub position: Vec2,
+    pub score: usize,
+    pub drop_rate: f32,
+    definition: Handle<EnemyDefinition>,
+}
+
+#[derive(Component)]
+#[require(CollisionLayers, Health, Hitbox, Sprite, Transform, Velocity)]
+pub struct Enemy {
+    pub name: String,
+    definition: Handle<EnemyDefinition>,
+    size: Vec2,
+    score: usize,
+    drop_rate: f32,
+    movement: MovementPattern,
+}
+
+#[derive(Component, Deref, DerefMut, Default)]
+pub struct Health(pub usize);
+
+impl EnemyDefinitions {
+    pub fn ids(&self) -> impl Iterator<Item = UntypedAssetId> + '_ {
+        self.0.iter().map(|handle| handle.id().untyped())
+    }
+
+    pub fn get<'a>(
+        &'a self,
+        name: &str,
+        enemy_definitions: &'a Assets<EnemyDefinition>,
+    ) -> Option<(Handle<EnemyDefinition>, &'a EnemyDefinition)> {
+        self.iter(enemy_definitions).find(|(_, definition)| definition.name == name)
+    }
+
+    pub fn iter<'a>(
+        &'a self,
+        enemy_definitions: &'a Assets<EnemyDefinition>,
+    ) -> impl Iterator<Item = (Handle<EnemyDefinition>, &'a EnemyDefinition)> + 'a {
+        self.0.iter().filter_map(|handle| {
+            enemy_definitions.get(handle).map(|definition| (handle.clone(), definition))
+        })
+    }
+}
+
+impl Enemy {
+    pub fn new(
+        handle: Handle<EnemyDefinition>,
+        definition: &EnemyDefinition,
+        image: Handle<Image>,
+        translation: Vec3,
+        direction: Vec2,
+        interval: f32,
+    ) -> (Self, BulletEmitter, CollisionLayers, Health, Hitbox, Sprite, Transform, Velocity) {
+        (
+            Self {
+                name: definition.name.clone(),
+                definition: handle,
+                size: definition.size,
+                score: definition.score,
+                drop_rate: definition.drop_rate,
+                movement: definition.movement,
+            },
+            BulletEmitter::new(&definition.emitter, interval),
+            CollisionLayers::ENEMY_BODY,
+            Health(definition.hp),
+            definition.hitbox.unwrap_or(Hitbox::rect(definition.size / definition.scale)),
+            Sprite::from_image(image),
+            Transform {
+                translation,
+                rotation: Quat::from_rotation_z(DEGREES.to_radians()),
+                scale: Vec3::splat(definition.scale),
+            },
+            Velocity(direction * definition.speed),
+        )
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    // debug!("setup");
+    let handles = PATHS.iter().map(|path| asset_server.load(*path)).collect();
+    commands.insert_resource(EnemyDefinitions(handles));
+}
+
+pub struct EnemyPlugin;
+
+impl Plugin for EnemyPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_asset::<EnemyDefinition>()
+            .init_asset_loader::<RonAssetLoader<EnemyDefinition>>()
+            .add_event::<EnemyDamageEvent>()
+            .add_event::<EnemyKilledEvent>()
+            .add_systems(Startup, setup)
+            .add_plugins(bullet::BulletPlugin)
+            .add_plugins(despawn::DespawnPlugin)
+            .add_plugins(emitter::EmitterPlugin)
+            .add_plugins(ship::ShipPlugin)
+            .add_plugins(sound::SoundPlugin)
+        ;
+    }
+}
@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+
+use crate::{
+    AppState,
+    GameConfig,
+    Score,
+    MyCamera,
+};
+use crate::ingame::{
+    CollisionSet,
+    TickSet,
+};
+use crate::ingame::enemy::{
+    EnemyDamageEvent,
+    EnemyKilledEvent,
+    Enemy,
+    Health,
+    MovementPattern,
+};
+use crate::ingame::utils::prelude::*;
+
+fn change_direction(
+    mut query: Query<(&Enemy, &mut Velocity, &Transform), With<Enemy>>,
+    config: Res<GameConfig>,
+) {
+    for (enemy, mut velocity, transform) in &mut query {
+        if enemy.movement != MovementPattern::Bounce { continue }
+
+        let left_window_collision =
+        config.window_size.x / 2.0 < transform.translation.x + enemy.size.x / 4.0;
+        let right_window_collision =
+        -config.window_size.x / 2.0 > transform.translation.x - enemy.size.x / 4.0;
+
+        if (left_window_collision && velocity.x > 0.0) || (right_window_collision && velocity.x < 0.0) {
+            // trace!("change_direction");
+            velocity.x = -velocity.x;
+        }
+    }
+}
+
+fn damage(
+    mut events: EventReader<EnemyDamageEvent>,
+    mut query: Query<&mut Health, With<Enemy>>,
+) {
+    for event in events.read() {
+        let Ok(mut health) = query.get_mut(event.0) else { continue };
+        // debug!("damage");
+        **health = health.saturating_sub(1);
+        // trace!("health: {}", **health);
+    }
+}
+
+fn despawn(
+    mut commands: Commands,
+    mut events: EventWriter<EnemyKilledEvent>,
+    mut score: ResMut<Score>,
+    query: Query<(Entity, &Enemy, &Health, &Transform), With<Enemy>>,
+) {
+    for (entity, enemy, health, transform) in &query {
+        if **health != 0 { continue }
+        // debug!("despawn");
+        events.send(EnemyKilledEvent {
+            name: enemy.name.clone(),
+            position: transform.translation.xy(),
+            score: enemy.score,
+            drop_rate: enemy.drop_rate,
+            definition: enemy.definition.clone(),
+        });
+        // trace!("send EnemyKilledEvent");
+        score.add(&enemy.name, enemy.score);
+        // trace!("score: {}", score.sum());
+        commands.entity(entity).despawn();
+    }
+}
+
+fn check_for_offscreen(
+    mut commands: Commands,
+    enemy_query: Query<(Entity, &Enemy, &Transform), (With<Enemy>, Without<MyCamera>)>,
+    camera_query: Query<&Transform, (With<MyCamera>, Without<Enemy>)>,
+    config: Res<GameConfig>,
+) {
+    let Ok(camera_transform) = camera_query.get_single() else { return };
+    let bottom_bound = camera_transform.translation.y - config.window_size.y / 2.0;
+
+    for (entity, enemy, transform) in &enemy_query {
+        if transform.translation.y + enemy.size.y / 2.0 >= bottom_bound { continue }
+        // debug!("check_for_offscreen");
+        commands.entity(entity).despawn();
+    }
+}
+
+fn all_despawn(
+    mut commands: Commands,
+    query: Query<Entity, With<Enemy>>,
+) {
+    // debug!("all_despawn");
+    for entity in &query { commands.entity(entity).despawn() }
+}
+
+pub struct ShipPlugin;
+
+impl Plugin for ShipPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(FixedUpdate, change_direction.in_set(TickSet::Enemy))
+            .add_systems(FixedUpdate, (
+                damage,
+                despawn,
+                check_for_offscreen,
+            ).chain().in_set(CollisionSet::Damage))
+            .add_systems(OnExit(AppState::Ingame), all_despawn)
+        ;
+    }
+}
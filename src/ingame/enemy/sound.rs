@@ -0,0 +1,34 @@
+use bevy::{
+    prelude::*,
+    audio::Volume,
+};
+
+use crate::loading::GameAssets;
+use crate::ingame::IngameState;
+use crate::ingame::enemy::EnemyDamageEvent;
+use crate::settings::Settings;
+
+fn play_damage_sound(
+    mut events: EventReader<EnemyDamageEvent>,
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    settings: Res<Settings>,
+) {
+    if events.is_empty() { return }
+    events.clear();
+    // debug!("play_damage_sound");
+    commands.spawn((
+        AudioPlayer(game_assets.hit_sound.clone()),
+        PlaybackSettings::DESPAWN.with_volume(Volume::new(settings.sfx_volume)),
+    ));
+}
+
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, play_damage_sound.run_if(in_state(IngameState::Playing)))
+        ;
+    }
+}
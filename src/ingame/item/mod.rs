@@ -0,0 +1,185 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    AppState,
+    GameConfig,
+    MyCamera,
+};
+use crate::locale::Strings;
+use crate::ingame::CollisionSet;
+use crate::ingame::enemy::EnemyKilledEvent;
+use crate::ingame::rng::GameRng;
+use crate::ingame::player::{
+    PLAYER_HP,
+    WEAPON_MAX,
+    Player,
+};
+use crate::ingame::utils::prelude::*;
+
+const SIZE: Vec2 = Vec2::splat(20.0);
+// the player's core is tiny, so items reach out to the whole ship instead
+const PICKUP_RADIUS: f32 = 24.0;
+const SPEED: f32 = 48.0;
+const DIRECTION: Vec2 = Vec2::new(0.0, -1.0);
+const TEXT_SIZE: f32 = 16.0;
+const TEXT_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
+// weights of power, ammo, heal and bomb
+const WEIGHTS: [(ItemKind, u32); 4] = [
+    (ItemKind::Power, 40),
+    (ItemKind::Ammo, 25),
+    (ItemKind::Heal, 20),
+    (ItemKind::Bomb, 15),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ItemKind {
+    Power,
+    Ammo,
+    Heal,
+    Bomb,
+}
+
+#[derive(Component)]
+#[require(CollisionLayers, Hitbox, Sprite, Transform, Velocity)]
+pub struct Item(pub ItemKind);
+
+#[derive(Event)]
+pub struct ItemPickupEvent(pub ItemKind);
+
+impl ItemKind {
+    fn random(rng: &mut impl Rng) -> Self {
+        let total: u32 = WEIGHTS.iter().map(|(_, weight)| weight).sum();
+        let mut roll = rng.gen_range(0..total);
+
+        for (kind, weight) in WEIGHTS {
+            if roll < weight { return kind }
+            roll -= weight;
+        }
+        ItemKind::Power
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            ItemKind::Power => "P",
+            ItemKind::Ammo => "A",
+            ItemKind::Heal => "H",
+            ItemKind::Bomb => "B",
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            ItemKind::Power => Color::srgb(0.9, 0.3, 0.3),
+            ItemKind::Ammo => Color::srgb(0.3, 0.6, 0.9),
+            ItemKind::Heal => Color::srgb(0.3, 0.9, 0.4),
+            ItemKind::Bomb => Color::srgb(0.9, 0.8, 0.3),
+        }
+    }
+
+    fn apply(&self, player: &mut Player) {
+        match self {
+            ItemKind::Power => player.weapon = (player.weapon + 1).min(WEAPON_MAX),
+            ItemKind::Ammo => player.bullets += 1,
+            ItemKind::Heal => player.hp = (player.hp + 1).min(PLAYER_HP),
+            ItemKind::Bomb => player.bombs += 1,
+        }
+    }
+}
+
+impl Item {
+    fn new(kind: ItemKind, translation: Vec3) -> (Self, CollisionLayers, Hitbox, Sprite, Transform, Velocity) {
+        (
+            Self(kind),
+            CollisionLayers::ITEM,
+            Hitbox::circle(PICKUP_RADIUS),
+            Sprite::from_color(kind.color(), SIZE),
+            Transform::from_translation(translation),
+            Velocity(DIRECTION * SPEED),
+        )
+    }
+}
+
+fn drop_item(
+    mut commands: Commands,
+    mut events: EventReader<EnemyKilledEvent>,
+    mut rng: ResMut<GameRng>,
+    strings: Res<Strings>,
+) {
+    for event in events.read() {
+        if !rng.gen_bool(event.drop_rate.clamp(0.0, 1.0) as f64) { continue }
+
+        let kind = ItemKind::random(&mut *rng);
+        // debug!("drop_item: {:?}", kind);
+        commands.spawn(Item::new(kind, event.position.extend(50.0)))
+            .with_child((
+                Text2d::new(kind.label()),
+                TextFont {
+                    font: strings.font(),
+                    font_size: TEXT_SIZE,
+                    ..Default::default()
+                },
+                TextColor(TEXT_COLOR),
+                Transform::from_xyz(0.0, 0.0, 1.0),
+            ));
+    }
+}
+
+fn pickup(
+    mut commands: Commands,
+    mut events: EventReader<CollisionEvent>,
+    mut pickup_events: EventWriter<ItemPickupEvent>,
+    mut player_query: Query<&mut Player, With<Player>>,
+    item_query: Query<&Item, With<Item>>,
+) {
+    for event in events.read() {
+        if event.layer != Layer::ITEM { continue }
+        let Ok(mut player) = player_query.get_mut(event.entity) else { continue };
+        let Ok(item) = item_query.get(event.other) else { continue };
+
+        // debug!("pickup: {:?}", item.0);
+        item.0.apply(&mut player);
+        pickup_events.send(ItemPickupEvent(item.0));
+        commands.entity(event.other).despawn_recursive();
+    }
+}
+
+fn check_for_offscreen(
+    mut commands: Commands,
+    camera_query: Query<&Transform, (With<MyCamera>, Without<Item>)>,
+    item_query: Query<(Entity, &Transform), (With<Item>, Without<MyCamera>)>,
+    config: Res<GameConfig>,
+) {
+    let Ok(camera_transform) = camera_query.get_single() else { return };
+    let bottom = camera_transform.translation.y - config.window_size.y / 2.0 - SIZE.y;
+
+    for (entity, transform) in &item_query {
+        if transform.translation.y > bottom { continue }
+        // debug!("check_for_offscreen");
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn all_despawn(
+    mut commands: Commands,
+    query: Query<Entity, With<Item>>,
+) {
+    // debug!("all_despawn");
+    for entity in &query { commands.entity(entity).despawn_recursive() }
+}
+
+pub struct ItemPlugin;
+
+impl Plugin for ItemPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<ItemPickupEvent>()
+            .add_systems(FixedUpdate, (
+                drop_item,
+                pickup,
+                check_for_offscreen,
+            ).chain().in_set(CollisionSet::Drop))
+            .add_systems(OnExit(AppState::Ingame), all_despawn)
+        ;
+    }
+}
@@ -1,26 +1,110 @@
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    ecs::schedule::ExecutorKind,
+};
 
-pub mod fighter;
-pub mod torpedo;
+use crate::AppState;
+
+pub mod enemy;
+pub mod stage;
+pub mod boss;
+pub mod item;
+pub mod rng;
 
 mod utils;
-mod player;
+pub mod player;
 mod camera;
 mod scoreboard;
+mod pause;
+
+pub use scoreboard::BossBarFill;
+pub use utils::{
+    POOL_ACTIVE,
+    POOL_FREE,
+    SpatialGrid,
+};
+pub use utils::prelude::{
+    CollisionEvent,
+    CollisionLayers,
+    Hitbox,
+    Layer,
+    Pool,
+    Pooled,
+    Shape,
+};
 
 const GRID_SIZE: f32 = 16.0;
 
+#[derive(SubStates, Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[source(AppState = AppState::Ingame)]
+pub enum IngameState {
+    #[default]
+    Playing,
+    Paused,
+}
+
+#[derive(SubStates, Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[source(IngameState = IngameState::Paused)]
+pub enum PauseMenuState {
+    #[default]
+    Main,
+    Options,
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TickSet {
+    Input,
+    Camera,
+    Player,
+    Enemy,
+    Movement,
+    Broadphase,
+    Collision,
+}
+
+// the order within TickSet::Collision, so a hit and what follows from it land on the same tick
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CollisionSet {
+    Hit,
+    Damage,
+    Drop,
+}
+
 pub struct IngamePlugin;
 
 impl Plugin for IngamePlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_plugins(fighter::FighterPlugin)
+            .add_sub_state::<IngameState>()
+            .add_sub_state::<PauseMenuState>()
+            // a single thread keeps the system order identical across runs
+            .edit_schedule(FixedUpdate, |schedule| {
+                schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+            })
+            .configure_sets(FixedUpdate, (
+                TickSet::Input,
+                TickSet::Camera,
+                TickSet::Player,
+                TickSet::Enemy,
+                TickSet::Movement,
+                TickSet::Broadphase,
+                TickSet::Collision,
+            ).chain().run_if(in_state(IngameState::Playing)))
+            .configure_sets(FixedUpdate, (
+                CollisionSet::Hit,
+                CollisionSet::Damage,
+                CollisionSet::Drop,
+            ).chain().in_set(TickSet::Collision))
+            .add_plugins(rng::RngPlugin)
+            .add_plugins(enemy::EnemyPlugin)
+            .add_plugins(boss::BossPlugin)
+            .add_plugins(item::ItemPlugin)
             .add_plugins(player::PlayerPlugin)
-            .add_plugins(torpedo::TorpedoPlugin)
+            .add_plugins(stage::StagePlugin)
             .add_plugins(utils::UtilsPlugin)
             .add_plugins(camera::CameraPlugin)
             .add_plugins(scoreboard::ScoreboardPlugin)
+            .add_plugins(pause::PausePlugin)
         ;
     }
 }
@@ -0,0 +1,220 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::action::{
+    Actions,
+    InputAction,
+};
+use crate::ingame::{
+    IngameState,
+    PauseMenuState,
+};
+use crate::locale::{
+    Localized,
+    Strings,
+};
+use crate::options::OptionsState;
+
+const TITLE_TEXT: &str = "pause.title";
+const TITLE_SIZE: f32 = 24.0;
+const TEXT_SIZE: f32 = 20.0;
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const SELECTED_COLOR: Color = Color::srgb(0.9, 0.8, 0.3);
+const OVERLAY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+const ROW_GAP: f32 = 12.0;
+const ITEMS: [PauseItem; 4] = [
+    PauseItem::Resume,
+    PauseItem::Restart,
+    PauseItem::Options,
+    PauseItem::Title,
+];
+
+#[derive(Component)]
+struct PauseMenu;
+
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+enum PauseItem {
+    Resume,
+    Restart,
+    Options,
+    Title,
+}
+
+#[derive(Resource, Deref, DerefMut, Default)]
+struct Selected(usize);
+
+impl PauseItem {
+    fn key(&self) -> &'static str {
+        match self {
+            PauseItem::Resume => "pause.resume",
+            PauseItem::Restart => "pause.restart",
+            PauseItem::Options => "pause.options",
+            PauseItem::Title => "pause.back_to_title",
+        }
+    }
+}
+
+fn toggle(
+    state: Res<State<IngameState>>,
+    mut next_state: ResMut<NextState<IngameState>>,
+    actions: Actions,
+) {
+    if !actions.just_pressed(InputAction::Pause) { return }
+
+    match state.get() {
+        IngameState::Playing => next_state.set(IngameState::Paused),
+        IngameState::Paused => next_state.set(IngameState::Playing),
+    }
+    // trace!("IngameState -> {:?}", next_state);
+}
+
+fn pause_time(
+    mut time: ResMut<Time<Virtual>>,
+) {
+    // debug!("pause_time");
+    time.pause();
+}
+
+fn unpause_time(
+    mut time: ResMut<Time<Virtual>>,
+) {
+    // debug!("unpause_time");
+    time.unpause();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut selected: ResMut<Selected>,
+    strings: Res<Strings>,
+) {
+    // debug!("setup");
+    let font = strings.font();
+    **selected = 0;
+
+    commands.spawn((
+        PauseMenu,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(ROW_GAP),
+            ..Default::default()
+        },
+        BackgroundColor(OVERLAY_COLOR),
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Localized(TITLE_TEXT),
+            Text::new(strings.get(TITLE_TEXT)),
+            TextFont {
+                font: font.clone(),
+                font_size: TITLE_SIZE,
+                ..Default::default()
+            },
+            TextColor(TEXT_COLOR),
+        ));
+        for item in ITEMS {
+            parent.spawn((
+                Button,
+                item,
+                Localized(item.key()),
+                Text::new(strings.get(item.key())),
+                TextFont {
+                    font: font.clone(),
+                    font_size: TEXT_SIZE,
+                    ..Default::default()
+                },
+                TextColor(TEXT_COLOR),
+            ));
+        }
+    });
+}
+
+fn navigate(
+    mut selected: ResMut<Selected>,
+    actions: Actions,
+    interaction_query: Query<(&Interaction, &PauseItem), Changed<Interaction>>,
+) {
+    if actions.just_pressed(InputAction::MoveUp) { **selected = (**selected + ITEMS.len() - 1) % ITEMS.len() }
+    if actions.just_pressed(InputAction::MoveDown) { **selected = (**selected + 1) % ITEMS.len() }
+
+    for (interaction, item) in &interaction_query {
+        if *interaction == Interaction::None { continue }
+        let Some(index) = ITEMS.iter().position(|i| i == item) else { continue };
+        **selected = index;
+    }
+}
+
+fn highlight(
+    selected: Res<Selected>,
+    mut query: Query<(&PauseItem, &mut TextColor), With<PauseItem>>,
+) {
+    for (item, mut color) in &mut query {
+        color.0 = if ITEMS[**selected] == *item { SELECTED_COLOR } else { TEXT_COLOR };
+    }
+}
+
+fn select(
+    selected: Res<Selected>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut next_ingame_state: ResMut<NextState<IngameState>>,
+    mut next_menu_state: ResMut<NextState<PauseMenuState>>,
+    mut next_options_state: ResMut<NextState<OptionsState>>,
+    actions: Actions,
+    interaction_query: Query<&Interaction, (With<PauseItem>, Changed<Interaction>)>,
+) {
+    let clicked = interaction_query.iter().any(|interaction| *interaction == Interaction::Pressed);
+
+    if !clicked && !actions.just_pressed(InputAction::Confirm) { return }
+
+    let item = ITEMS[**selected];
+    // trace!("select: {:?}", item);
+    match item {
+        PauseItem::Resume => next_ingame_state.set(IngameState::Playing),
+        PauseItem::Restart => next_app_state.set(AppState::Restart),
+        PauseItem::Options => {
+            next_menu_state.set(PauseMenuState::Options);
+            next_options_state.set(OptionsState::General);
+        }
+        PauseItem::Title => next_app_state.set(AppState::Mainmenu),
+    }
+}
+
+fn close_options(
+    mut next_state: ResMut<NextState<PauseMenuState>>,
+) {
+    // debug!("close_options");
+    next_state.set(PauseMenuState::Main);
+}
+
+fn all_despawn(
+    mut commands: Commands,
+    query: Query<Entity, With<PauseMenu>>,
+) {
+    // debug!("all_despawn");
+    for entity in &query { commands.entity(entity).despawn_recursive() }
+}
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Selected>()
+            .add_systems(OnEnter(IngameState::Paused), pause_time)
+            .add_systems(OnEnter(PauseMenuState::Main), setup)
+            .add_systems(Update, toggle.run_if(in_state(AppState::Ingame).and(not(in_state(PauseMenuState::Options)))))
+            .add_systems(Update, (
+                navigate,
+                highlight,
+                select,
+            ).chain().run_if(in_state(PauseMenuState::Main)))
+            .add_systems(OnExit(PauseMenuState::Main), all_despawn)
+            .add_systems(OnEnter(OptionsState::Closed), close_options.run_if(in_state(PauseMenuState::Options)))
+            .add_systems(OnExit(IngameState::Paused), unpause_time)
+        ;
+    }
+}
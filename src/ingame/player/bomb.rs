@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+
+use crate::{
+    AppState,
+    GameConfig,
+    MyCamera,
+};
+use crate::ingame::{
+    IngameState,
+    TickSet,
+};
+use crate::ingame::enemy::{
+    EnemyDamageEvent,
+    Enemy,
+};
+use crate::ingame::player::{
+    BombEvent,
+    Invulnerable,
+    Player,
+    PlayerInput,
+};
+use crate::ingame::utils::prelude::*;
+
+const DAMAGE: usize = 3;
+const INVULNERABLE_SECONDS: f32 = 2.0;
+const FLASH_SECONDS: f32 = 0.5;
+const FLASH_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.8);
+
+#[derive(Component, Deref, DerefMut)]
+struct BombFlash(Timer);
+
+fn event(
+    mut events: EventWriter<BombEvent>,
+    mut player_query: Query<&mut Player, With<Player>>,
+    input: Res<PlayerInput>,
+) {
+    if !input.bomb { return }
+
+    let Ok(mut player) = player_query.get_single_mut() else { return };
+
+    if player.bombs == 0 { return }
+    // debug!("event");
+    events.send_default();
+    player.bombs -= 1;
+    // trace!("player.bombs: {}", player.bombs);
+}
+
+fn detonate(
+    mut commands: Commands,
+    mut events: EventReader<BombEvent>,
+    mut damage_events: EventWriter<EnemyDamageEvent>,
+    player_query: Query<Entity, With<Player>>,
+    bullet_query: Query<(&CollisionLayers, Entity), With<Bullet>>,
+    enemy_query: Query<(Entity, &Transform), (With<Enemy>, Without<MyCamera>)>,
+    camera_query: Query<&Transform, (With<MyCamera>, Without<Enemy>)>,
+    config: Res<GameConfig>,
+) {
+    if events.is_empty() { return }
+    events.clear();
+
+    let Ok(camera_transform) = camera_query.get_single() else { return };
+    let camera_pos = camera_transform.translation.xy();
+    let half_size = config.window_size / 2.0;
+    // debug!("detonate");
+    for (layers, bullet_entity) in &bullet_query {
+        if !layers.memberships.contains(Layer::ENEMY_SHOT) { continue }
+        commands.release(bullet_entity);
+    }
+    for (enemy_entity, enemy_transform) in &enemy_query {
+        let offset = (enemy_transform.translation.xy() - camera_pos).abs();
+
+        if offset.x > half_size.x || offset.y > half_size.y { continue }
+        damage_events.send_batch((0..DAMAGE).map(|_| EnemyDamageEvent(enemy_entity)));
+    }
+    if let Ok(player_entity) = player_query.get_single() {
+        commands.entity(player_entity).insert(Invulnerable::new(INVULNERABLE_SECONDS));
+    }
+    commands.spawn((
+        BombFlash(Timer::from_seconds(FLASH_SECONDS, TimerMode::Once)),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..Default::default()
+        },
+        BackgroundColor(FLASH_COLOR),
+    ));
+}
+
+fn fade_flash(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut BombFlash, &mut BackgroundColor), With<BombFlash>>,
+    time: Res<Time>,
+) {
+    for (entity, mut flash, mut color) in &mut query {
+        flash.tick(time.delta());
+        color.0 = FLASH_COLOR.with_alpha(FLASH_COLOR.alpha() * flash.fraction_remaining());
+
+        if flash.finished() {
+            // debug!("fade_flash");
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn all_despawn(
+    mut commands: Commands,
+    query: Query<Entity, With<BombFlash>>,
+) {
+    // debug!("all_despawn");
+    for entity in &query { commands.entity(entity).despawn() }
+}
+
+pub struct BombPlugin;
+
+impl Plugin for BombPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(FixedUpdate, (
+                event,
+                detonate,
+            ).chain().in_set(TickSet::Player))
+            .add_systems(Update, fade_flash.run_if(in_state(IngameState::Playing)))
+            .add_systems(OnExit(AppState::Ingame), all_despawn)
+        ;
+    }
+}
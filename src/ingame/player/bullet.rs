@@ -1,47 +1,61 @@
 use bevy::prelude::*;
 
 use crate::AppState;
+use crate::atlas::AtlasLayouts;
+use crate::loading::GameAssets;
+use crate::ingame::TickSet;
 use crate::ingame::GRID_SIZE;
 use crate::ingame::player::{
     ShootEvent,
     Player,
+    PlayerInput,
 };
 use crate::ingame::utils::prelude::*;
 
-const PATH_IMAGE: &str = "ittoku-2dshooting-game/player-bullet.png";
 const IMAGE_SIZE: UVec2 = UVec2::splat(32);
 const COLUMN: u32 = 4;
 const ROW: u32 = 1;
 const DIRECTION: Vec2 = Vec2::new(0.0, 1.0);
 const SPEED: f32 = 512.0;
 const FPS: f32 = 0.1;
-const SIZE: Vec2 = Vec2::splat(32.0);
+// the laser's hitbox stretches with its scale
+const HITBOX: Hitbox = Hitbox::rect(Vec2::splat(32.0));
 const DEGREES: f32 = 0.0;
 const SCALE: Vec3 = Vec3::splat(1.0);
-const KEYCODE: KeyCode = KeyCode::Space;
+const DOUBLE_OFFSET: f32 = GRID_SIZE / 2.0;
+const SPREAD_DEGREES: f32 = 15.0;
+const LASER_SPEED: f32 = 1024.0;
+const LASER_SCALE: Vec3 = Vec3::new(0.5, 3.0, 1.0);
 
-#[derive(Resource, Deref)]
-struct BulletImage(Handle<Image>);
+#[derive(Resource)]
+struct BulletImage {
+    image: Handle<Image>,
+    layout: Handle<TextureAtlasLayout>,
+}
 
 fn setup(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut atlas_layouts: ResMut<AtlasLayouts>,
+    game_assets: Res<GameAssets>,
 ) {
     // debug!("setup");
-    let handle: Handle<Image> = asset_server.load(PATH_IMAGE);
-    commands.insert_resource(BulletImage(handle));
+    commands.insert_resource(BulletImage {
+        image: game_assets.player_bullet.clone(),
+        layout: atlas_layouts.grid(&mut texture_atlas_layouts, IMAGE_SIZE, COLUMN, ROW),
+    });
 }
 
 fn event(
     mut events: EventWriter<ShootEvent>,
     mut player_query: Query<&mut Player, With<Player>>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input: Res<PlayerInput>,
 ) {
-    if !keyboard_input.just_pressed(KEYCODE) { return }
+    if !input.shoot { return }
 
     let Ok(mut player) = player_query.get_single_mut() else { return };
 
-    if player.bullets <= 0 { return }
+    if player.bullets == 0 { return }
     // debug!("event");
     events.send_default();
     player.bullets -= 1;
@@ -50,37 +64,54 @@ fn event(
 
 fn shoot(
     mut commands: Commands,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut pool: ResMut<Pool>,
     mut events: EventReader<ShootEvent>,
-    player_query: Query<&Transform, With<Player>>,
+    player_query: Query<(&Player, &Transform), With<Player>>,
     bullet_image: Res<BulletImage>,
 ) {
     if events.is_empty() { return }
     events.clear();
 
-    let Ok(transform) = player_query.get_single() else { return };
-    let layout = TextureAtlasLayout::from_grid(IMAGE_SIZE, COLUMN, ROW, None, None);
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
+    let Ok((player, transform)) = player_query.get_single() else { return };
     let translation = Vec3::new(
         transform.translation.x, 
         transform.translation.y + GRID_SIZE * 2.0, 
         99.0,
     );
+    // (x offset, degrees, speed, scale)
+    let volley = match player.weapon {
+        0 => vec![(0.0, DEGREES, SPEED, SCALE)],
+        1 => vec![
+            (-DOUBLE_OFFSET, DEGREES, SPEED, SCALE),
+            (DOUBLE_OFFSET, DEGREES, SPEED, SCALE),
+        ],
+        2 => vec![
+            (0.0, DEGREES, SPEED, SCALE),
+            (0.0, SPREAD_DEGREES, SPEED, SCALE),
+            (0.0, -SPREAD_DEGREES, SPEED, SCALE),
+        ],
+        _ => vec![(0.0, DEGREES, LASER_SPEED, LASER_SCALE)],
+    };
 
-    let animation_config = AnimationConfig::new(AnimationName::Bullet, 0, 3, FPS);
-    let velocity = Velocity(DIRECTION * SPEED);
-    let bullet = Bullet::new(
-        Shooter::Player, 
-        SIZE, 
-        bullet_image.clone(), 
-        texture_atlas_layout.clone(), 
-        animation_config.first_sprite_index, 
-        translation, 
-        DEGREES, 
-        SCALE,
-    );
-    // debug!("shoot");
-    commands.spawn((bullet, animation_config, velocity));
+    for (i, (offset, degrees, speed, scale)) in volley.into_iter().enumerate() {
+        let animation_config = AnimationConfig::new(AnimationName::Bullet, 0, 3, FPS);
+        let direction = Vec2::from_angle(degrees.to_radians()).rotate(DIRECTION);
+        let velocity = Velocity(direction * speed);
+        let bullet = Bullet::new(
+            CollisionLayers::PLAYER_SHOT,
+            HITBOX,
+            bullet_image.image.clone(), 
+            bullet_image.layout.clone(), 
+            animation_config.first_sprite_index, 
+            translation + Vec3::X * offset, 
+            degrees, 
+            scale,
+        );
+        // debug!("shoot");
+        let entity = pool.spawn(&mut commands, Pooled::Bullet, (bullet, animation_config, velocity));
+        // only one bullet per volley returns ammo
+        if i == 0 { commands.entity(entity).insert(Refund); }
+    }
 }
 
 pub struct BulletPlugin;
@@ -89,10 +120,10 @@ impl Plugin for BulletPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_systems(OnEnter(AppState::Ingame), setup)
-            .add_systems(Update, (
+            .add_systems(FixedUpdate, (
                 event,
                 shoot,
-            ).run_if(in_state(AppState::Ingame)))
+            ).chain().in_set(TickSet::Player))
         ;
     }
 }
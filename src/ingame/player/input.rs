@@ -0,0 +1,74 @@
+use bevy::{
+    prelude::*,
+    input::InputSystem,
+};
+
+use crate::action::{
+    Actions,
+    InputAction,
+};
+use crate::ingame::IngameState;
+use crate::ingame::player::PlayerInput;
+
+const DEADZONE: f32 = 0.2;
+
+fn read_actions(
+    mut input: ResMut<PlayerInput>,
+    actions: Actions,
+) {
+    input.direction = Vec2::new(
+        actions.axis(InputAction::MoveLeft, InputAction::MoveRight),
+        actions.axis(InputAction::MoveDown, InputAction::MoveUp),
+    );
+    // presses are kept until the next tick consumes them
+    input.shoot |= actions.just_pressed(InputAction::Shoot);
+    input.bomb |= actions.just_pressed(InputAction::Bomb);
+}
+
+fn read_stick(
+    mut input: ResMut<PlayerInput>,
+    gamepads: Query<&Gamepad>,
+) {
+    for gamepad in &gamepads {
+        input.direction += deadzone(gamepad.left_stick());
+    }
+    input.direction = input.direction.clamp(Vec2::NEG_ONE, Vec2::ONE);
+}
+
+// rescale the stick so movement starts from zero at the edge of the deadzone
+fn deadzone(stick: Vec2) -> Vec2 {
+    let length = stick.length();
+
+    if length < DEADZONE { return Vec2::ZERO }
+    stick / length * ((length - DEADZONE) / (1.0 - DEADZONE)).min(1.0)
+}
+
+fn consume(
+    mut input: ResMut<PlayerInput>,
+) {
+    input.shoot = false;
+    input.bomb = false;
+}
+
+fn reset(
+    mut input: ResMut<PlayerInput>,
+) {
+    // debug!("reset");
+    *input = PlayerInput::default();
+}
+
+pub struct InputPlugin;
+
+impl Plugin for InputPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<PlayerInput>()
+            .add_systems(PreUpdate, (
+                read_actions,
+                read_stick,
+            ).chain().after(InputSystem).run_if(in_state(IngameState::Playing)))
+            .add_systems(FixedPostUpdate, consume.run_if(in_state(IngameState::Playing)))
+            .add_systems(OnExit(IngameState::Playing), reset)
+        ;
+    }
+}
@@ -1,24 +1,53 @@
 use bevy::prelude::*;
 
 pub mod ship;
+mod bomb;
 mod bullet;
+mod input;
 mod sound;
 
 pub const PLAYER_HP: usize = 8;
+pub const WEAPON_MAX: usize = 3;
 
 #[derive(Event, Default)]
 struct ShootEvent;
 
+#[derive(Event, Default)]
+pub struct BombEvent;
+
+#[derive(Event)]
+pub struct PlayerDamagedEvent {
+    pub hp: usize,
+    pub knockback: Vec2,
+}
+
+#[derive(Resource, Clone, Copy, Default, Debug, PartialEq)]
+pub struct PlayerInput {
+    pub direction: Vec2,
+    pub shoot: bool,
+    pub bomb: bool,
+}
+
 #[derive(Component)]
 pub struct Player {
     pub hp: usize,
-    pub size: Vec2,
     pub bullets: usize,
+    pub weapon: usize,
+    pub bombs: usize,
 }
 
+#[derive(Component, Deref, DerefMut)]
+pub struct Invulnerable(Timer);
+
 impl Player {
-    fn new(hp: usize, size: Vec2, bullets: usize) -> Self {
-        Self { hp, size, bullets, }
+    fn new(hp: usize, bullets: usize, bombs: usize) -> Self {
+        Self { hp, bullets, weapon: 0, bombs, }
+    }
+}
+
+impl Invulnerable {
+    pub fn new(seconds: f32) -> Self {
+        Self(Timer::from_seconds(seconds, TimerMode::Once))
     }
 }
 
@@ -28,7 +57,11 @@ impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_event::<ShootEvent>()
+            .add_event::<BombEvent>()
+            .add_event::<PlayerDamagedEvent>()
+            .add_plugins(input::InputPlugin)
             .add_plugins(ship::ShipPlugin)
+            .add_plugins(bomb::BombPlugin)
             .add_plugins(bullet::BulletPlugin)
             .add_plugins(sound::SoundPlugin)
         ;
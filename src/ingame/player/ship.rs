@@ -1,39 +1,52 @@
 use bevy::prelude::*;
 
 use crate::{
-    WINDOW_SIZE,
     AppState,
+    GameConfig,
     MyCamera,
 };
+use crate::atlas::AtlasLayouts;
+use crate::loading::GameAssets;
+use crate::ingame::{
+    CollisionSet,
+    TickSet,
+};
 use crate::ingame::GRID_SIZE;
-use crate::ingame::camera::SPEED as CAMERA_SPEED;
+use crate::ingame::camera::ScrollLock;
 use crate::ingame::player::{
     PLAYER_HP as HP,
+    Invulnerable,
     Player,
+    PlayerDamagedEvent,
+    PlayerInput,
 };
 use crate::ingame::utils::prelude::*;
 
-const PATH_IMAGE: &str = "ittoku-2dshooting-game/player-ship.png";
 const IMAGE_SIZE: UVec2 = UVec2::splat(32);
 const SIZE: Vec2 = Vec2::splat(32.0);
+// only the cockpit gets hit, in the sprite's own pixels
+const CORE_HITBOX: Hitbox = Hitbox::circle(2.0);
 const BULLETS: usize = 2;
+const BOMBS: usize = 2;
 const COLUMN: u32 = 4;
 const ROW: u32 = 1;
 const TRANSLATION: Vec3 = Vec3::new(0.0, GRID_SIZE * -12.0, 99.0);
 const SCALE: Vec3 = Vec3::splat(2.0);
 const SPEED: f32 = 256.0;
+const BLINK_INTERVAL: f32 = 0.1;
+const KNOCKBACK: f32 = GRID_SIZE;
 
 fn setup(
     mut commands: Commands,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
-    asset_server: Res<AssetServer>,
+    mut atlas_layouts: ResMut<AtlasLayouts>,
+    game_assets: Res<GameAssets>,
 ) {
-    let texture = asset_server.load(PATH_IMAGE);
-    let layout = TextureAtlasLayout::from_grid(IMAGE_SIZE, COLUMN, ROW, None, None);
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
+    let texture = game_assets.player_ship.clone();
+    let texture_atlas_layout = atlas_layouts.grid(&mut texture_atlas_layouts, IMAGE_SIZE, COLUMN, ROW);
 
     let animation_indices = AnimationConfig::new(AnimationName::PlayerDamage, 0, 3, 0.0);
-    let player = Player::new(HP, SIZE, BULLETS);
+    let player = Player::new(HP, BULLETS, BOMBS);
     // debug!("setup");
     commands.spawn((
         Sprite::from_atlas_image(
@@ -50,47 +63,70 @@ fn setup(
         },
         animation_indices,
         player,
+        CollisionLayers::PLAYER_BODY,
+        CORE_HITBOX,
     ));
 }
 
 fn movement(
     mut player_query: Query<&mut Transform, (With<Player>, Without<MyCamera>)>,
     camera_query: Query<&Transform, (With<MyCamera>, Without<Player>)>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    time_step: Res<Time<Fixed>>,
+    input: Res<PlayerInput>,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    scroll: Res<ScrollLock>,
 ) {
-    let mut direction = Vec2::ZERO;
-    // set direction
-    for key in keyboard_input.get_pressed() {
-        match key {
-            KeyCode::ArrowLeft  | KeyCode::KeyA => direction.x -= 1.0,
-            KeyCode::ArrowRight | KeyCode::KeyD => direction.x += 1.0,
-            KeyCode::ArrowUp    | KeyCode::KeyW => direction.y += 1.0,
-            KeyCode::ArrowDown  | KeyCode::KeyS => direction.y -= 1.0,
-            _ => {},
-        }
-    }
-
+    let direction = input.direction;
     let Ok(mut ship_transform) = player_query.get_single_mut() else { return };
     let ship_xy = ship_transform.translation.xy();
     let Ok(camera_transform) = camera_query.get_single() else { return };
     let camera_xy = camera_transform.translation.xy();
     // set player x position
     let new_player_position_x = ship_xy.x
-    + direction.x * SPEED * time_step.delta().as_secs_f32();
+    + direction.x * SPEED * time.delta_secs();
     // set player x range movement
-    let left_bound = camera_xy.x - WINDOW_SIZE.x / 2.0 + SIZE.x;
-    let right_bound = camera_xy.x + WINDOW_SIZE.x / 2.0 - SIZE.x;
+    let left_bound = camera_xy.x - config.window_size.x / 2.0 + SIZE.x;
+    let right_bound = camera_xy.x + config.window_size.x / 2.0 - SIZE.x;
     // set player y position
     let new_player_position_y = ship_xy.y
-    + direction.y * SPEED * time_step.delta().as_secs_f32();
+    + direction.y * SPEED * time.delta_secs();
     // set player y range movement
-    let down_bound = camera_xy.y - WINDOW_SIZE.y / 2.0 + SIZE.y;
-    let up_bound = camera_xy.y + WINDOW_SIZE.y / 2.0 - SIZE.y;
+    let down_bound = camera_xy.y - config.window_size.y / 2.0 + SIZE.y;
+    let up_bound = camera_xy.y + config.window_size.y / 2.0 - SIZE.y;
     // move player
     ship_transform.translation.x = new_player_position_x.clamp(left_bound, right_bound);
     ship_transform.translation.y = new_player_position_y.clamp(down_bound, up_bound);
-    ship_transform.translation.y += CAMERA_SPEED;
+    ship_transform.translation.y += scroll.speed() * time.delta_secs();
+}
+
+fn invulnerable(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Invulnerable, &mut Visibility), With<Player>>,
+    time: Res<Time>,
+) {
+    let Ok((entity, mut invulnerable, mut visibility)) = query.get_single_mut() else { return };
+
+    if invulnerable.tick(time.delta()).finished() {
+        // debug!("invulnerable");
+        commands.entity(entity).remove::<Invulnerable>();
+        *visibility = Visibility::Inherited;
+        return
+    }
+    // blink while invulnerable
+    let blink = ((invulnerable.elapsed_secs() / BLINK_INTERVAL) as u32).is_multiple_of(2);
+    visibility.set_if_neq(if blink { Visibility::Hidden } else { Visibility::Inherited });
+}
+
+fn knockback(
+    mut events: EventReader<PlayerDamagedEvent>,
+    mut query: Query<&mut Transform, With<Player>>,
+) {
+    let Ok(mut transform) = query.get_single_mut() else { return };
+
+    for event in events.read() {
+        // debug!("knockback");
+        transform.translation += (event.knockback * KNOCKBACK).extend(0.0);
+    }
 }
 
 fn despawn(
@@ -100,7 +136,7 @@ fn despawn(
 ) {
     let Ok((entity, player)) = query.get_single() else { return };
 
-    if player.hp <= 0 {
+    if player.hp == 0 {
         // debug!("despawn");
         commands.entity(entity).despawn();
         // trace!("AppState Ingame -> Gameover");
@@ -108,16 +144,27 @@ fn despawn(
     }
 }
 
+fn all_despawn(
+    mut commands: Commands,
+    query: Query<Entity, With<Player>>,
+) {
+    // debug!("all_despawn");
+    for entity in &query { commands.entity(entity).despawn() }
+}
+
 pub struct ShipPlugin;
 
 impl Plugin for ShipPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_systems(OnEnter(AppState::Ingame), setup)
-            .add_systems(Update, (
+            .add_systems(FixedUpdate, (
+                knockback,
                 movement,
-                despawn,
-            ).run_if(in_state(AppState::Ingame)))
+                invulnerable,
+            ).chain().in_set(TickSet::Player))
+            .add_systems(FixedUpdate, despawn.in_set(CollisionSet::Damage))
+            .add_systems(OnExit(AppState::Ingame), all_despawn)
         ;
     }
 }
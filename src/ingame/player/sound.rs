@@ -1,34 +1,45 @@
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    audio::Volume,
+};
+
+use crate::loading::GameAssets;
+use crate::settings::Settings;
+use crate::ingame::IngameState;
+use crate::ingame::player::{
+    PlayerDamagedEvent,
+    ShootEvent,
+};
 
-use crate::AppState;
-use crate::ingame::player::ShootEvent;
-
-const PATH_SOUND_SHOOT: &str = "sounds/battle-shooting-1.ogg";
-
-#[derive(Resource, Deref)]
-struct ShootSound(Handle<AudioSource>);
-
-fn setup(
+fn play_shoot_sound(
+    mut events: EventReader<ShootEvent>,
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    game_assets: Res<GameAssets>,
+    settings: Res<Settings>,
 ) {
-    // debug!("setup");
-    let handle = asset_server.load(PATH_SOUND_SHOOT);
-    commands.insert_resource(ShootSound(handle));
+    if events.is_empty() { return }
+    events.clear();
+
+    // debug!("play_shoot_sound");
+    commands.spawn((
+        AudioPlayer(game_assets.shoot_sound.clone()),
+        PlaybackSettings::DESPAWN.with_volume(Volume::new(settings.sfx_volume)),
+    ));
 }
 
-fn play_shoot_sound(
-    mut events: EventReader<ShootEvent>,
+fn play_damage_sound(
+    mut events: EventReader<PlayerDamagedEvent>,
     mut commands: Commands,
-    sound: Res<ShootSound>,
+    game_assets: Res<GameAssets>,
+    settings: Res<Settings>,
 ) {
     if events.is_empty() { return }
     events.clear();
 
-    // debug!("play_shoot_sound");
+    // debug!("play_damage_sound");
     commands.spawn((
-        AudioPlayer(sound.clone()),
-        PlaybackSettings::DESPAWN,
+        AudioPlayer(game_assets.hit_sound.clone()),
+        PlaybackSettings::DESPAWN.with_volume(Volume::new(settings.sfx_volume)),
     ));
 }
 
@@ -37,8 +48,10 @@ pub struct SoundPlugin;
 impl Plugin for SoundPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(OnEnter(AppState::Ingame), setup)
-            .add_systems(Update, play_shoot_sound.run_if(in_state(AppState::Ingame)))
+            .add_systems(Update, (
+                play_shoot_sound,
+                play_damage_sound,
+            ).run_if(in_state(IngameState::Playing)))
         ;
     }
 }
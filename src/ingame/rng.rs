@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+use rand::{
+    RngCore,
+    SeedableRng,
+    rngs::StdRng,
+};
+
+use crate::AppState;
+use crate::ingame::stage::CurrentStage;
+
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, rng: StdRng::seed_from_u64(seed), }
+    }
+
+    pub fn seed(&self) -> u64 { self.seed }
+
+    // every stage starts from its own stream so one stage can be reproduced on its own
+    fn reseed(&mut self, stage: usize) {
+        self.rng = StdRng::seed_from_u64(self.seed.wrapping_add(stage as u64));
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self { Self::new(rand::random()) }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 { self.rng.next_u32() }
+
+    fn next_u64(&mut self) -> u64 { self.rng.next_u64() }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) { self.rng.fill_bytes(dest) }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> { self.rng.try_fill_bytes(dest) }
+}
+
+fn setup(
+    mut rng: ResMut<GameRng>,
+    stage: Res<CurrentStage>,
+) {
+    // debug!("setup: {} stage {}", rng.seed(), **stage);
+    rng.reseed(**stage);
+}
+
+pub struct RngPlugin;
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<GameRng>()
+            .add_systems(OnEnter(AppState::Ingame), setup)
+        ;
+    }
+}
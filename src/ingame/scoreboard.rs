@@ -1,19 +1,28 @@
 use bevy::prelude::*;
 
 use crate::{
-    WINDOW_SIZE,
-    PATH_FONT,
     AppState,
+    GameConfig,
     Score,
 };
+use crate::loading::GameAssets;
+use crate::locale::{
+    Localized,
+    Strings,
+};
+use crate::ingame::IngameState;
+use crate::ingame::boss::Boss;
+use crate::ingame::enemy::Health;
+use crate::ingame::item::ItemPickupEvent;
 use crate::ingame::player::{
     PLAYER_HP,
     Player,
+    PlayerDamagedEvent,
 };
 
-const PATH_IMAGE: &str = "images/hp-heart.png";
-const SCORE_TEXT: &str = "スコア: ";
-const LIFE_TEXT: &str = "ライフ: ";
+const SCORE_TEXT: &str = "common.score";
+const LIFE_TEXT: &str = "scoreboard.life";
+const BOMB_TEXT: &str = "scoreboard.bomb";
 const LIFE_TEXT_WIDTH: f32 = 60.0;
 const HEART_SIZE: Vec2 = Vec2::splat(12.8);
 const HEART_SCALE: Vec3 = Vec3::splat(0.2);
@@ -22,9 +31,16 @@ const HEART_WRAP: usize = 5;
 const TEXT_SIZE: f32 = 20.0;
 const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
 const PADDING: f32 = 5.0;
-
-#[derive(Resource, Deref, DerefMut)]
-struct HeartCount(usize);
+const BOSS_BAR_SIZE: Vec2 = Vec2::new(400.0, 8.0);
+const BOSS_BAR_TOP: f32 = 40.0;
+const BOSS_BAR_BACKGROUND: Color = Color::srgb(0.2, 0.2, 0.2);
+const BOSS_BAR_MARKER: Color = Color::srgb(0.9, 0.9, 0.9);
+const BOSS_BAR_MARKER_WIDTH: f32 = 2.0;
+const BOSS_PHASE_COLORS: [Color; 3] = [
+    Color::srgb(0.3, 0.8, 0.3),
+    Color::srgb(0.9, 0.7, 0.2),
+    Color::srgb(0.9, 0.2, 0.2),
+];
 
 #[derive(Component)]
 struct ScoreboardUi;
@@ -32,9 +48,18 @@ struct ScoreboardUi;
 #[derive(Component)]
 struct ScoreText;
 
+#[derive(Component)]
+struct BombText;
+
 #[derive(Component)]
 struct Heart(usize);
 
+#[derive(Component)]
+struct BossBar;
+
+#[derive(Component)]
+pub struct BossBarFill;
+
 impl ScoreboardUi {
     fn new_text(
         text: String,
@@ -106,22 +131,28 @@ impl ScoreboardUi {
 
 fn setup(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    game_assets: Res<GameAssets>,
+    config: Res<GameConfig>,
+    strings: Res<Strings>,
 ) {
     // debug!("setup");
-    let font = asset_server.load(PATH_FONT);
+    let font = strings.font();
+    let window_size = config.window_size;
     // score
     let (top, left) = (
         Val::Px(PADDING),
         Val::Px(PADDING),
     );
-    commands.spawn(ScoreboardUi::new_text(
-        SCORE_TEXT.to_string(),
-        font.clone(),
-        TEXT_SIZE,
-        TEXT_COLOR,
-        top,
-        left,
+    commands.spawn((
+        ScoreboardUi::new_text(
+            strings.get(SCORE_TEXT).to_string(),
+            font.clone(),
+            TEXT_SIZE,
+            TEXT_COLOR,
+            top,
+            left,
+        ),
+        Localized(SCORE_TEXT),
     ))
     .with_child((
         ScoreboardUi::new_span(
@@ -131,32 +162,59 @@ fn setup(
         ),
         ScoreText,
     ));
+    // player bombs
+    let (top, left) = (
+        Val::Px(PADDING * 2.0 + TEXT_SIZE),
+        Val::Px(PADDING),
+    );
+    commands.spawn((
+        ScoreboardUi::new_text(
+            strings.get(BOMB_TEXT).to_string(),
+            font.clone(),
+            TEXT_SIZE,
+            TEXT_COLOR,
+            top,
+            left,
+        ),
+        Localized(BOMB_TEXT),
+    ))
+    .with_child((
+        ScoreboardUi::new_span(
+            font.clone(),
+            TEXT_SIZE,
+            TEXT_COLOR,
+        ),
+        BombText,
+    ));
     // player hp
     let (top, left) = (
         Val::Px(PADDING),
-        Val::Px(WINDOW_SIZE.x / 2.0 + PADDING),
+        Val::Px(window_size.x / 2.0 + PADDING),
     );
-    commands.spawn(ScoreboardUi::new_text(
-        LIFE_TEXT.to_string(), 
-        font.clone(), 
-        TEXT_SIZE, 
-        TEXT_COLOR, 
-        top, 
-        left,
+    commands.spawn((
+        ScoreboardUi::new_text(
+            strings.get(LIFE_TEXT).to_string(),
+            font.clone(),
+            TEXT_SIZE,
+            TEXT_COLOR,
+            top,
+            left,
+        ),
+        Localized(LIFE_TEXT),
     ));
     // player hp heart
-    let image = asset_server.load(PATH_IMAGE);
+    let image = game_assets.heart.clone();
     for i in 1..PLAYER_HP + 1 {
         let (mut top, mut left) = (
             Val::Px(PADDING - HEART_MARGIN),
             Val::Px(
-                WINDOW_SIZE.x / 2.0 + PADDING + LIFE_TEXT_WIDTH - HEART_MARGIN +
+                window_size.x / 2.0 + PADDING + LIFE_TEXT_WIDTH - HEART_MARGIN +
                 HEART_SIZE.x * i as f32),
         );
         if i > HEART_WRAP {
             top = Val::Px(PADDING - HEART_MARGIN + HEART_SIZE.y);
             left = Val::Px(
-                WINDOW_SIZE.x / 2.0 + PADDING + LIFE_TEXT_WIDTH - HEART_MARGIN +
+                window_size.x / 2.0 + PADDING + LIFE_TEXT_WIDTH - HEART_MARGIN +
                 HEART_SIZE.x * (i - HEART_WRAP) as f32);
         }
         commands.spawn((
@@ -180,21 +238,105 @@ fn update_score(
     **span = score.sum().to_string();
 }
 
+fn update_bombs(
+    mut query: Query<&mut TextSpan, With<BombText>>,
+    player_query: Query<&Player, Changed<Player>>,
+) {
+    let Ok(player) = player_query.get_single() else { return };
+    let Ok(mut span) = query.get_single_mut() else { return };
+    // update bombs
+    **span = player.bombs.to_string();
+}
+
 fn update_playerhp(
-    mut commands: Commands,
-    mut count: ResMut<HeartCount>,
-    heart_query: Query<(&Heart, Entity), With<Heart>>,
+    mut damaged_events: EventReader<PlayerDamagedEvent>,
+    mut pickup_events: EventReader<ItemPickupEvent>,
+    mut heart_query: Query<(&Heart, &mut Visibility), With<Heart>>,
     player_query: Query<&Player, With<Player>>,
 ) {
+    if damaged_events.is_empty() && pickup_events.is_empty() { return }
+    damaged_events.clear();
+    pickup_events.clear();
+
     let Ok(player) = player_query.get_single() else { return };
 
-    if player.hp == **count { return }
+    for (heart, mut visibility) in &mut heart_query {
+        let new_visibility = if heart.0 <= player.hp { Visibility::Inherited } else { Visibility::Hidden };
+        // trace!("heart {}: {:?}", heart.0, new_visibility);
+        visibility.set_if_neq(new_visibility);
+    }
+}
+
+fn spawn_boss_bar(
+    mut commands: Commands,
+    boss_query: Query<&Boss, Added<Boss>>,
+    config: Res<GameConfig>,
+) {
+    let Ok(boss) = boss_query.get_single() else { return };
+    // debug!("spawn_boss_bar");
+    let left = (config.window_size.x - BOSS_BAR_SIZE.x) / 2.0;
 
-    for (heart, heart_entity) in &heart_query {
-        if player.hp != heart.0 - 1 { continue }
-        **count -= 1;
-        // trace!("count: {}", **count);
-        commands.entity(heart_entity).despawn();
+    commands.spawn((
+        ScoreboardUi,
+        BossBar,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(BOSS_BAR_TOP),
+            left: Val::Px(left),
+            width: Val::Px(BOSS_BAR_SIZE.x),
+            height: Val::Px(BOSS_BAR_SIZE.y),
+            ..Default::default()
+        },
+        BackgroundColor(BOSS_BAR_BACKGROUND),
+    ))
+    .with_children(|parent| {
+        // the children go with the bar, so only the bar is tagged for all_despawn
+        parent.spawn((
+            BossBarFill,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..Default::default()
+            },
+            BackgroundColor(BOSS_PHASE_COLORS[0]),
+        ));
+        // phase thresholds
+        for phase in boss.phases.iter().filter(|phase| phase.threshold < 1.0) {
+            parent.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(phase.threshold * 100.0),
+                    width: Val::Px(BOSS_BAR_MARKER_WIDTH),
+                    height: Val::Percent(100.0),
+                    ..Default::default()
+                },
+                BackgroundColor(BOSS_BAR_MARKER),
+            ));
+        }
+    });
+}
+
+fn update_boss_bar(
+    boss_query: Query<(&Boss, &Health), With<Boss>>,
+    mut fill_query: Query<(&mut Node, &mut BackgroundColor), With<BossBarFill>>,
+) {
+    let Ok((boss, health)) = boss_query.get_single() else { return };
+    let Ok((mut node, mut color)) = fill_query.get_single_mut() else { return };
+
+    node.width = Val::Percent(**health as f32 / boss.max_hp as f32 * 100.0);
+    color.0 = BOSS_PHASE_COLORS[boss.phase.min(BOSS_PHASE_COLORS.len() - 1)];
+}
+
+fn despawn_boss_bar(
+    mut commands: Commands,
+    boss_query: Query<(), With<Boss>>,
+    bar_query: Query<Entity, With<BossBar>>,
+) {
+    if !boss_query.is_empty() { return }
+
+    for entity in &bar_query {
+        // debug!("despawn_boss_bar");
+        commands.entity(entity).despawn_recursive();
     }
 }
 
@@ -203,17 +345,13 @@ fn reset_score(mut score: ResMut<Score>) {
     *score = Score::reset();
 }
 
-fn reset_count(mut count: ResMut<HeartCount>) {
-    // debug!("reset_count");
-    **count = PLAYER_HP;
-}
-
 fn all_despawn(
     mut commands: Commands,
-    query: Query<Entity, With<ScoreboardUi>>,
+    query: Query<Entity, (With<ScoreboardUi>, Without<Parent>)>,
 ) {
     // debug!("all_despawn");
-    for entity in &query { commands.entity(entity).despawn() }
+    // the text spans go with their parent text
+    for entity in &query { commands.entity(entity).despawn_recursive() }
 }
 
 pub struct ScoreboardPlugin;
@@ -221,17 +359,18 @@ pub struct ScoreboardPlugin;
 impl Plugin for ScoreboardPlugin {
     fn build(&self, app: &mut App) {
         app
-            .insert_resource(HeartCount(PLAYER_HP))
             .add_systems(OnEnter(AppState::Ingame), setup)
             .add_systems(Update, (
                 update_score,
+                update_bombs,
                 update_playerhp,
-            ).run_if(in_state(AppState::Ingame)))
+                spawn_boss_bar,
+                update_boss_bar,
+                despawn_boss_bar,
+            ).run_if(in_state(IngameState::Playing)))
             .add_systems(OnExit(AppState::Ingame), all_despawn)
-            .add_systems(OnExit(AppState::Gameover), (
-                reset_score,
-                reset_count,
-            ))
+            .add_systems(OnEnter(AppState::Mainmenu), reset_score)
+            .add_systems(OnEnter(AppState::Restart), reset_score)
         ;
     }
 }
@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+
+use crate::{
+    AppState,
+    Score,
+    MyCamera,
+};
+use crate::locale::Strings;
+use crate::ingame::stage::{
+    CurrentStage,
+    StageDefinition,
+    StageDefinitions,
+};
+
+const CLEAR_TEXT: &str = "stage.clear";
+const CLEAR_SIZE: f32 = 20.0;
+const SCORE_TEXT: &str = "common.score";
+const BOARD_SIZE: Vec2 = Vec2::new(360.0, 180.0);
+const BOARD_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const TEXT_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
+const TEXT_SIZE: f32 = 16.0;
+const TEXT_PADDING: f32 = 30.0;
+const DURATION: f32 = 3.0;
+
+#[derive(Component)]
+struct Stageclear;
+
+#[derive(Resource, Deref, DerefMut)]
+struct ClearTimer(Timer);
+
+fn setup(
+    mut commands: Commands,
+    camera_query: Query<&Transform, With<MyCamera>>,
+    score: Res<Score>,
+    stage: Res<CurrentStage>,
+    definitions: Res<StageDefinitions>,
+    stage_definitions: Res<Assets<StageDefinition>>,
+    strings: Res<Strings>,
+) {
+    // debug!("setup");
+    let Ok(camera_transform) = camera_query.get_single() else { return };
+    let camera_y = camera_transform.translation.y;
+    let font = strings.font();
+    // stage names are keys into the string table
+    let name = definitions.get(**stage)
+        .and_then(|handle| stage_definitions.get(&handle))
+        .map_or(String::new(), |definition| strings.get(&definition.name).to_string());
+    // stage clear
+    commands.spawn((
+        Text2d::new(format!("{} {}", name, strings.get(CLEAR_TEXT))),
+        TextFont {
+            font: font.clone(),
+            font_size: CLEAR_SIZE,
+            ..Default::default()
+        },
+        TextColor(TEXT_COLOR),
+        Transform::from_xyz(0.0, camera_y + TEXT_PADDING, 0.0),
+        Stageclear,
+    ));
+    // score
+    commands.spawn((
+        Text2d::new(format!("{}{}", strings.get(SCORE_TEXT), score.sum())),
+        TextFont {
+            font: font.clone(),
+            font_size: TEXT_SIZE,
+            ..Default::default()
+        },
+        TextColor(TEXT_COLOR),
+        Transform::from_xyz(0.0, camera_y - TEXT_PADDING, 0.0),
+        Stageclear,
+    ));
+    // board
+    commands.spawn((
+        Sprite {
+            color: BOARD_COLOR,
+            custom_size: Some(BOARD_SIZE),
+            ..Default::default()
+        },
+        Transform::from_xyz(0.0, camera_y, -10.0),
+        Stageclear,
+    ));
+    commands.insert_resource(ClearTimer(Timer::from_seconds(DURATION, TimerMode::Once)));
+}
+
+fn update(
+    mut timer: ResMut<ClearTimer>,
+    mut next_state: ResMut<NextState<AppState>>,
+    time: Res<Time>,
+) {
+    if !timer.tick(time.delta()).just_finished() { return }
+    // trace!("AppState StageClear -> Ingame");
+    next_state.set(AppState::Ingame);
+}
+
+fn next_stage(mut stage: ResMut<CurrentStage>) {
+    // debug!("next_stage");
+    **stage += 1;
+    // trace!("stage: {}", **stage);
+}
+
+fn all_despawn(
+    mut commands: Commands,
+    query: Query<Entity, With<Stageclear>>,
+) {
+    // debug!("all_despawn");
+    for entity in &query { commands.entity(entity).despawn() }
+}
+
+pub struct ClearPlugin;
+
+impl Plugin for ClearPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(AppState::StageClear), setup)
+            .add_systems(Update, update.run_if(in_state(AppState::StageClear)))
+            .add_systems(OnExit(AppState::StageClear), (
+                next_stage,
+                all_despawn,
+            ))
+        ;
+    }
+}
@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::ron_asset::RonAsset;
+
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct StageDefinition {
+    pub name: String,
+    pub waves: Vec<Wave>,
+    #[serde(default)]
+    pub boss: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Wave {
+    pub trigger: WaveTrigger,
+    pub enemy: String,
+    pub count: usize,
+    pub formation: Formation,
+    pub side: EntrySide,
+    #[serde(default)]
+    pub offset: f32,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum WaveTrigger {
+    Time(f32),
+    Distance(f32),
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Formation {
+    Line,
+    Column,
+    V,
+    Random,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum EntrySide {
+    Top,
+    Left,
+    Right,
+}
+
+impl RonAsset for StageDefinition {
+    const EXTENSIONS: &'static [&'static str] = &["stage.ron"];
+}
@@ -0,0 +1,75 @@
+use bevy::{
+    prelude::*,
+    asset::UntypedAssetId,
+};
+
+use crate::AppState;
+use crate::ron_asset::RonAssetLoader;
+
+mod clear;
+mod definition;
+mod timeline;
+
+pub use definition::{
+    StageDefinition,
+    Wave,
+    WaveTrigger,
+    Formation,
+    EntrySide,
+};
+
+const PATHS: [&str; 2] = [
+    "stages/stage1.stage.ron",
+    "stages/stage2.stage.ron",
+];
+
+#[derive(Resource)]
+pub struct StageDefinitions(Vec<Handle<StageDefinition>>);
+
+#[derive(Resource, Deref, DerefMut, Default, Debug)]
+pub struct CurrentStage(pub usize);
+
+impl StageDefinitions {
+    pub fn ids(&self) -> impl Iterator<Item = UntypedAssetId> + '_ {
+        self.0.iter().map(|handle| handle.id().untyped())
+    }
+
+    pub fn get(&self, index: usize) -> Option<Handle<StageDefinition>> {
+        self.0.get(index % self.0.len()).cloned()
+    }
+
+    pub fn len(&self) -> usize { self.0.len() }
+
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    // debug!("setup");
+    let handles = PATHS.iter().map(|path| asset_server.load(*path)).collect();
+    commands.insert_resource(StageDefinitions(handles));
+}
+
+fn reset_stage(mut stage: ResMut<CurrentStage>) {
+    // debug!("reset_stage");
+    **stage = 0;
+}
+
+pub struct StagePlugin;
+
+impl Plugin for StagePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_asset::<StageDefinition>()
+            .init_asset_loader::<RonAssetLoader<StageDefinition>>()
+            .init_resource::<CurrentStage>()
+            .add_systems(Startup, setup)
+            .add_systems(OnEnter(AppState::Mainmenu), reset_stage)
+            .add_systems(OnEnter(AppState::Restart), reset_stage)
+            .add_plugins(clear::ClearPlugin)
+            .add_plugins(timeline::TimelinePlugin)
+        ;
+    }
+}
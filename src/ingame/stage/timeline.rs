@@ -0,0 +1,235 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    AppState,
+    GameConfig,
+    MyCamera,
+};
+use crate::ingame::TickSet;
+use crate::ingame::GRID_SIZE;
+use crate::ingame::boss::Boss;
+use crate::ingame::rng::GameRng;
+use crate::ingame::enemy::{
+    EnemyDefinition,
+    EnemyDefinitions,
+    Enemy,
+};
+use crate::ingame::stage::{
+    CurrentStage,
+    StageDefinition,
+    StageDefinitions,
+    Wave,
+    WaveTrigger,
+    Formation,
+    EntrySide,
+};
+
+const SPACING: f32 = GRID_SIZE * 4.0;
+const TOP_Y: f32 = GRID_SIZE * 11.0;
+const SIDE_Y: f32 = GRID_SIZE * 6.0;
+const SIDE_MARGIN: f32 = GRID_SIZE * 2.0;
+const RANDOM_ALONG_TOP: f32 = GRID_SIZE * 18.0;
+const RANDOM_ALONG_SIDE: f32 = GRID_SIZE * 6.0;
+const RANDOM_DEPTH: f32 = GRID_SIZE * 2.0;
+const BOSS_Y: f32 = GRID_SIZE * 9.0;
+
+#[derive(Resource)]
+pub struct StageTimeline {
+    stage: Handle<StageDefinition>,
+    elapsed: f32,
+    spawned: Vec<bool>,
+    boss_pending: bool,
+}
+
+impl StageTimeline {
+    fn new(stage: Handle<StageDefinition>) -> Self {
+        Self { stage, elapsed: 0.0, spawned: Vec::new(), boss_pending: false, }
+    }
+
+    fn waves_spawned(&self) -> bool {
+        !self.spawned.is_empty() && self.spawned.iter().all(|spawned| *spawned)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.waves_spawned() && !self.boss_pending
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    definitions: Res<StageDefinitions>,
+    stage: Res<CurrentStage>,
+) {
+    // debug!("setup");
+    if definitions.is_empty() { return }
+    let Some(handle) = definitions.get(**stage) else { return };
+    commands.insert_resource(StageTimeline::new(handle));
+}
+
+fn advance(
+    mut commands: Commands,
+    mut timeline: ResMut<StageTimeline>,
+    mut rng: ResMut<GameRng>,
+    asset_server: Res<AssetServer>,
+    stage_definitions: Res<Assets<StageDefinition>>,
+    definitions: Res<EnemyDefinitions>,
+    enemy_definitions: Res<Assets<EnemyDefinition>>,
+    camera_query: Query<&Transform, With<MyCamera>>,
+    config: Res<GameConfig>,
+    time: Res<Time>,
+) {
+    let Some(stage) = stage_definitions.get(&timeline.stage) else { return };
+    let Ok(camera_transform) = camera_query.get_single() else { return };
+    let camera_y = camera_transform.translation.y;
+
+    if timeline.spawned.len() != stage.waves.len() {
+        timeline.spawned = vec![false; stage.waves.len()];
+        timeline.boss_pending = stage.boss.is_some();
+    }
+    timeline.elapsed += time.delta_secs();
+
+    for (i, wave) in stage.waves.iter().enumerate() {
+        if timeline.spawned[i] { continue }
+
+        let triggered = match wave.trigger {
+            WaveTrigger::Time(seconds) => timeline.elapsed >= seconds,
+            WaveTrigger::Distance(distance) => camera_y >= distance,
+        };
+        if !triggered { continue }
+
+        timeline.spawned[i] = true;
+        let Some((handle, definition)) = definitions.get(&wave.enemy, &enemy_definitions) else {
+            warn!("unknown enemy in stage {}: {}", stage.name, wave.enemy);
+            continue
+        };
+        // debug!("spawn wave {}", i);
+        spawn_wave(
+            &mut commands,
+            &mut rng,
+            &asset_server,
+            wave,
+            handle,
+            definition,
+            camera_y,
+            config.window_size,
+        );
+    }
+}
+
+fn spawn_wave(
+    commands: &mut Commands,
+    rng: &mut GameRng,
+    asset_server: &AssetServer,
+    wave: &Wave,
+    handle: Handle<EnemyDefinition>,
+    definition: &EnemyDefinition,
+    camera_y: f32,
+    window_size: Vec2,
+) {
+    let random_along = if wave.side == EntrySide::Top { RANDOM_ALONG_TOP } else { RANDOM_ALONG_SIDE };
+    let direction = definition.direction;
+
+    for i in 0..wave.count {
+        let mid = (wave.count as f32 - 1.0) / 2.0;
+        let index = i as f32 - mid;
+        let (along, depth) = match wave.formation {
+            Formation::Line => (index * SPACING, 0.0),
+            Formation::Column => (0.0, i as f32 * SPACING),
+            Formation::V => (index * SPACING, index.abs() * SPACING / 2.0),
+            Formation::Random => (
+                rng.gen_range(-random_along..random_along),
+                rng.gen_range(0.0..RANDOM_DEPTH),
+            ),
+        };
+        let (position, direction) = match wave.side {
+            EntrySide::Top => (
+                Vec2::new(wave.offset + along, camera_y + TOP_Y + depth),
+                direction,
+            ),
+            EntrySide::Left => (
+                Vec2::new(
+                    -window_size.x / 2.0 - SIDE_MARGIN - depth,
+                    camera_y + SIDE_Y + wave.offset + along,
+                ),
+                Vec2::new(direction.x.abs(), direction.y),
+            ),
+            EntrySide::Right => (
+                Vec2::new(
+                    window_size.x / 2.0 + SIDE_MARGIN + depth,
+                    camera_y + SIDE_Y + wave.offset + along,
+                ),
+                Vec2::new(-direction.x.abs(), direction.y),
+            ),
+        };
+        commands.spawn(Enemy::new(
+            handle.clone(),
+            definition,
+            asset_server.load(&definition.sprite),
+            position.extend(10.0),
+            direction,
+            definition.emitter.interval(rng),
+        ));
+    }
+}
+
+fn spawn_boss(
+    mut commands: Commands,
+    mut timeline: ResMut<StageTimeline>,
+    mut rng: ResMut<GameRng>,
+    asset_server: Res<AssetServer>,
+    stage_definitions: Res<Assets<StageDefinition>>,
+    definitions: Res<EnemyDefinitions>,
+    enemy_definitions: Res<Assets<EnemyDefinition>>,
+    enemy_query: Query<(), With<Enemy>>,
+    camera_query: Query<&Transform, With<MyCamera>>,
+) {
+    if !timeline.boss_pending || !timeline.waves_spawned() || !enemy_query.is_empty() { return }
+    let Some(stage) = stage_definitions.get(&timeline.stage) else { return };
+    let Ok(camera_transform) = camera_query.get_single() else { return };
+
+    timeline.boss_pending = false;
+    let Some(name) = &stage.boss else { return };
+    let Some((handle, definition)) = definitions.get(name, &enemy_definitions) else {
+        warn!("unknown boss in stage {}: {}", stage.name, name);
+        return
+    };
+    let position = Vec2::new(0.0, camera_transform.translation.y + BOSS_Y);
+    // debug!("spawn_boss");
+    commands.spawn((
+        Enemy::new(
+            handle,
+            definition,
+            asset_server.load(&definition.sprite),
+            position.extend(10.0),
+            definition.direction,
+            definition.emitter.interval(&mut *rng),
+        ),
+        Boss::new(definition),
+    ));
+}
+
+fn check_clear(
+    timeline: Res<StageTimeline>,
+    enemy_query: Query<(), With<Enemy>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if !timeline.is_finished() || !enemy_query.is_empty() { return }
+    // trace!("AppState Ingame -> StageClear");
+    next_state.set(AppState::StageClear);
+}
+
+pub struct TimelinePlugin;
+
+impl Plugin for TimelinePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(AppState::Ingame), setup)
+            .add_systems(FixedUpdate, (
+                check_clear,
+                advance,
+                spawn_boss,
+            ).chain().in_set(TickSet::Enemy).run_if(resource_exists::<StageTimeline>))
+        ;
+    }
+}
@@ -1,7 +1,8 @@
 use bevy::prelude::*;
 
-use crate::AppState;
+use crate::ingame::IngameState;
 use crate::ingame::player::Player;
+use crate::ingame::utils::pool::ReleaseCommands;
 
 #[derive(Component)]
 pub struct AnimationConfig {
@@ -70,7 +71,7 @@ fn despawn_animation(
         if config.frame_timer.just_finished() {
             if let Some(atlas) = &mut sprite.texture_atlas {
                 if atlas.index == config.last_sprite_index {
-                    commands.entity(entity).despawn();
+                    commands.release(entity);
                 } else {
                     atlas.index += 1;
                     config.frame_timer = AnimationConfig::timer_from_fps(config.fps);
@@ -106,7 +107,7 @@ impl Plugin for AnimationConfigPlugin {
                 bullet_animation,
                 despawn_animation,
                 player_damage_animation,
-            ).run_if(in_state(AppState::Ingame)))
+            ).run_if(in_state(IngameState::Playing)))
         ;
     }
 }
@@ -1,49 +1,43 @@
-use bevy::{
-    prelude::*,
-    math::bounding::{Aabb2d, IntersectsVolume},
-    window::PrimaryWindow,
-};
+use bevy::prelude::*;
 
 use crate::{
-    AppState,
+    GameConfig,
     MyCamera,
 };
-use crate::ingame::player::Player;
-use crate::ingame::fighter::{
-    FighterDamageEvent,
-    Fighter,
+use crate::ingame::CollisionSet;
+use crate::ingame::player::{
+    Invulnerable,
+    Player,
+    PlayerDamagedEvent,
 };
-use crate::ingame::torpedo::{
-    TorpedoDamageEvent,
-    Torpedo,
+use crate::ingame::enemy::{
+    EnemyDamageEvent,
+    Enemy,
 };
+use crate::ingame::utils::prelude::*;
 
 #[derive(Component)]
-#[require(Sprite, Transform)]
-pub struct Bullet {
-    shooter: Shooter,
-    pub size: Vec2,
-}
+#[require(CollisionLayers, Hitbox, Sprite, Transform)]
+pub struct Bullet;
 
-#[derive(PartialEq)]
-pub enum Shooter {
-    Player,
-    Enemy,
-}
+#[derive(Component)]
+pub struct Refund;
 
 impl Bullet {
     pub fn new(
-        shooter: Shooter,
-        size: Vec2,
+        layers: CollisionLayers,
+        hitbox: Hitbox,
         image: Handle<Image>,
         layout: Handle<TextureAtlasLayout>,
         first_index: usize,
         translation: Vec3,
         degrees: f32,
         scale: Vec3,
-    ) -> (Self, Sprite, Transform) {
+    ) -> (Self, CollisionLayers, Hitbox, Sprite, Transform) {
         (
-            Self { shooter, size, },
+            Self,
+            layers,
+            hitbox,
             Self::sprite(image, layout, first_index),
             Self::transform(translation, degrees, scale)
         )
@@ -78,114 +72,88 @@ impl Bullet {
 
 fn check_for_hit_player(
     mut commands: Commands,
-    bullet_query: Query<(&Bullet, Entity, &Transform), (With<Bullet>, Without<Player>)>,
-    mut player_query: Query<(&mut Player, &Transform), (With<Player>, Without<Bullet>)>,
+    mut events: EventReader<CollisionEvent>,
+    mut damaged_events: EventWriter<PlayerDamagedEvent>,
+    mut player_query: Query<(&mut Player, &Transform), (With<Player>, Without<Invulnerable>)>,
+    transform_query: Query<&Transform, Without<Player>>,
+    bullet_query: Query<(), With<Bullet>>,
+    config: Res<GameConfig>,
 ) {
-    let Ok((mut player, player_transform)) = player_query.get_single_mut() else { return };
-    let player_pos = player_transform.translation.xy();
-
-    for (bullet, bullet_entity, bullet_transform) in &bullet_query {
-        if bullet.shooter == Shooter::Player { continue }
-
-        let bullet_pos = bullet_transform.translation.xy();
-
-        let collision = Aabb2d::new(bullet_pos, bullet.size / 2.0)
-            .intersects(&Aabb2d::new(player_pos, player.size / 2.0));
-
-        if collision {
-            // debug!("check_for_hit_player");
-            player.hp -= 1;
-            // trace!("player.hp: {}", player.hp);
-            commands.entity(bullet_entity).despawn();
-        }
+    let mut is_hit = false;
+
+    for event in events.read() {
+        // one hit per frame, the rest are absorbed by the invulnerability
+        if is_hit || !(Layer::ENEMY_SHOT | Layer::HAZARD).contains(event.layer) { continue }
+        let Ok((mut player, player_transform)) = player_query.get_mut(event.entity) else { continue };
+        // already down, and gone once the damage set runs
+        if player.hp == 0 { continue }
+        let Ok(other_transform) = transform_query.get(event.other) else { continue };
+
+        // debug!("check_for_hit_player");
+        let player_pos = player_transform.translation.xy();
+        let other_pos = other_transform.translation.xy();
+        player.hp = player.hp.saturating_sub(1);
+        player.weapon = player.weapon.saturating_sub(1);
+        // trace!("player.hp: {}", player.hp);
+        // hazards stay where they are, bullets are used up
+        if bullet_query.contains(event.other) { commands.release(event.other) }
+        commands.entity(event.entity).insert(Invulnerable::new(config.invulnerable_duration));
+        damaged_events.send(PlayerDamagedEvent {
+            hp: player.hp,
+            knockback: (player_pos - other_pos).normalize_or(Vec2::NEG_Y),
+        });
+        is_hit = true;
     }
 }
 
-fn check_for_hit_fighter(
+fn check_for_hit_enemy(
     mut commands: Commands,
-    mut events: EventWriter<FighterDamageEvent>,
+    mut events: EventReader<CollisionEvent>,
+    mut damage_events: EventWriter<EnemyDamageEvent>,
     mut player_query: Query<&mut Player, With<Player>>,
-    bullet_query: Query<(&Bullet, Entity, &Transform), (With<Bullet>, Without<Fighter>)>,
-    fighter_query: Query<(&Fighter, Entity, &Transform), (With<Fighter>, Without<Bullet>)>,
+    bullet_query: Query<Has<Refund>, With<Bullet>>,
+    enemy_query: Query<(), With<Enemy>>,
 ) {
-    for (bullet, bullet_entity, bullet_transform) in &bullet_query {
-        if bullet.shooter != Shooter::Player { continue }
+    let mut last_bullet = None;
 
-        let bullet_pos = bullet_transform.translation.xy();
-        let mut is_hit_bullet = false;
+    for event in events.read() {
+        if event.layer != Layer::ENEMY_BODY { continue }
+        let Ok(refund) = bullet_query.get(event.entity) else { continue };
+        if !enemy_query.contains(event.other) { continue }
 
-        for (fighter, fighter_entity, fighter_transform) in &fighter_query {
-            let fighter_pos = fighter_transform.translation.xy();
+        // debug!("check_for_hit_enemy");
+        damage_events.send(EnemyDamageEvent(event.other));
 
-            let collision = Aabb2d::new(bullet_pos, bullet.size / 2.0)
-                .intersects(&Aabb2d::new(fighter_pos, fighter.size / 2.0));
+        // a bullet overlapping several enemies hits all of them, but is used up once
+        if last_bullet == Some(event.entity) { continue }
+        last_bullet = Some(event.entity);
+        commands.release(event.entity);
 
-            if collision {
-                // debug!("check_for_hit_fighter");
-                is_hit_bullet = true;
-                events.send(FighterDamageEvent(fighter_entity));
-            }
-        }
-        if is_hit_bullet {
-            let Ok(mut player) = player_query.get_single_mut() else { return };
+        if !refund { continue }
+        let Ok(mut player) = player_query.get_single_mut() else { continue };
 
-            player.bullets += 1;
-            // trace!("player.bullets: {}", player.bullets);
-            commands.entity(bullet_entity).despawn();
-        }
-    }
-}
-
-fn check_for_hit_torpedo(
-    mut commands: Commands,
-    mut events: EventWriter<TorpedoDamageEvent>,
-    mut player_query: Query<&mut Player, With<Player>>,
-    bullet_query: Query<(&Bullet, Entity, &Transform), (With<Bullet>, Without<Torpedo>)>,
-    torpedo_query: Query<(&Torpedo, Entity, &Transform), (With<Torpedo>, Without<Bullet>)>,
-) {
-    for (bullet, bullet_entity, bullet_transform) in &bullet_query {
-        if bullet.shooter != Shooter::Player { continue }
-
-        let bullet_pos = bullet_transform.translation.xy();
-        let mut is_hit_bullet = false;
-
-        for (torpedo, torpedo_entity, torpedo_transform) in &torpedo_query {
-            let torpedo_pos = torpedo_transform.translation.xy();
-            let collision = Aabb2d::new(bullet_pos, bullet.size / 2.0)
-                .intersects(&Aabb2d::new(torpedo_pos, torpedo.size / 2.0));
-
-            if collision {
-                // debug!("check_for_hit_torpedo");
-                is_hit_bullet = true;
-                events.send(TorpedoDamageEvent(torpedo_entity));
-            }
-        }
-        if is_hit_bullet {
-            let Ok(mut player) = player_query.get_single_mut() else { return };
-
-            player.bullets += 1;
-            // trace!("player.bullets: {}", player.bullets);
-            commands.entity(bullet_entity).despawn();
-        }
+        player.bullets += 1;
+        // trace!("player.bullets: {}", player.bullets);
     }
 }
 
 fn check_for_offscreen(
     mut commands: Commands,
     mut player_query: Query<&mut Player, With<Player>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<&Transform, (With<MyCamera>, Without<Bullet>)>,
-    bullet_query: Query<(&Bullet, Entity, &Transform), (With<Bullet>, Without<MyCamera>)>,
+    bullet_query: Query<(&Hitbox, Entity, &Transform, Has<Refund>), (With<Bullet>, Without<MyCamera>)>,
+    config: Res<GameConfig>,
 ) {
-    let Ok(window) = window_query.get_single() else { return };
-    let (window_width, window_height) = (window.width(), window.height());
+    let (window_width, window_height) = (config.window_size.x, config.window_size.y);
     let Ok(camera_transform) = camera_query.get_single() else { return };
     let camera_pos = camera_transform.translation.xy();
 
-    for (bullet, bullet_entity, bullet_transform) in &bullet_query {
+    for (hitbox, bullet_entity, bullet_transform, refund) in &bullet_query {
         let bullet_x = bullet_transform.translation.x;
         let bullet_y = bullet_transform.translation.y;
-        let (bullet_width, bullet_height) = (bullet.size.x, bullet.size.y);
+        let aabb = hitbox.aabb(bullet_transform);
+        let size = aabb.max - aabb.min;
+        let (bullet_width, bullet_height) = (size.x, size.y);
         let left_bound   = camera_pos.x - window_width  / 2.0 - bullet_width  / 2.0;
         let right_bound  = camera_pos.x + window_width  / 2.0 + bullet_width  / 2.0;
         let bottom_bound = camera_pos.y - window_height / 2.0 - bullet_height / 2.0;
@@ -193,37 +161,27 @@ fn check_for_offscreen(
 
         if bullet_x <= left_bound || bullet_x >= right_bound
         || bullet_y <= bottom_bound || bullet_y >= top_bound {
-            if bullet.shooter == Shooter::Player {
+            if refund {
                 let Ok(mut player) = player_query.get_single_mut() else { return };
                 // debug!("check_for_offscreen");
                 player.bullets += 1;
                 // trace!("player.bullets: {}", player.bullets);
             }
-            commands.entity(bullet_entity).despawn();
+            commands.release(bullet_entity);
         }
     }
 }
 
-fn all_despawn(
-    mut commands: Commands,
-    query: Query<Entity, With<Bullet>>,
-) {
-    // debug!("all_despawn");
-    for entity in &query { commands.entity(entity).despawn() }
-}
-
 pub struct BulletPlugin;
 
 impl Plugin for BulletPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(Update, (
+            .add_systems(FixedUpdate, (
                 check_for_hit_player,
-                check_for_hit_fighter,
-                check_for_hit_torpedo,
+                check_for_hit_enemy,
                 check_for_offscreen,
-            ).run_if(in_state(AppState::Ingame)))
-            .add_systems(OnExit(AppState::Ingame), all_despawn)
+            ).chain().in_set(CollisionSet::Hit))
         ;
     }
 }
@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+use std::ops::BitOr;
+
+use crate::ingame::TickSet;
+use crate::ingame::utils::hitbox::Hitbox;
+use crate::ingame::utils::spatial_grid::SpatialGrid;
+
+const LAYERS: usize = 6;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Layer(u32);
+
+// which layers an entity is on, and which layers it looks for; every overlap is reported
+// once, from the side whose mask has the other's layer
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CollisionLayers {
+    pub memberships: Layer,
+    pub mask: Layer,
+}
+
+// `entity` touched `other`, which is on `layer`
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct CollisionEvent {
+    pub entity: Entity,
+    pub other: Entity,
+    pub layer: Layer,
+}
+
+// one grid per layer, rebuilt once per tick
+#[derive(Resource, Default)]
+struct Broadphase([SpatialGrid; LAYERS]);
+
+impl Layer {
+    pub const NONE: Self = Self(0);
+    pub const PLAYER_BODY: Self = Self(1 << 0);
+    pub const PLAYER_SHOT: Self = Self(1 << 1);
+    pub const ENEMY_BODY: Self = Self(1 << 2);
+    pub const ENEMY_SHOT: Self = Self(1 << 3);
+    pub const ITEM: Self = Self(1 << 4);
+    pub const HAZARD: Self = Self(1 << 5);
+
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn intersects(&self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    // the single layers this one is made of
+    fn iter(self) -> impl Iterator<Item = Self> {
+        (0..LAYERS).map(|bit| Self(1 << bit)).filter(move |layer| self.contains(*layer))
+    }
+
+    fn index(&self) -> usize {
+        self.0.trailing_zeros() as usize
+    }
+}
+
+impl BitOr for Layer {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self { self.union(other) }
+}
+
+impl CollisionLayers {
+    pub const PLAYER_BODY: Self = Self::new(
+        Layer::PLAYER_BODY,
+        Layer::ENEMY_SHOT.union(Layer::ITEM).union(Layer::HAZARD),
+    );
+    pub const PLAYER_SHOT: Self = Self::new(Layer::PLAYER_SHOT, Layer::ENEMY_BODY);
+    pub const ENEMY_BODY: Self = Self::new(Layer::ENEMY_BODY, Layer::NONE);
+    pub const ENEMY_SHOT: Self = Self::new(Layer::ENEMY_SHOT, Layer::NONE);
+    pub const ITEM: Self = Self::new(Layer::ITEM, Layer::NONE);
+    pub const HAZARD: Self = Self::new(Layer::HAZARD, Layer::NONE);
+
+    pub const fn new(memberships: Layer, mask: Layer) -> Self {
+        Self { memberships, mask, }
+    }
+}
+
+fn rebuild(
+    mut broadphase: ResMut<Broadphase>,
+    query: Query<(Entity, &CollisionLayers, &Hitbox, &Transform)>,
+) {
+    for grid in &mut broadphase.0 { grid.clear() }
+
+    for (entity, layers, hitbox, transform) in &query {
+        for layer in layers.memberships.iter() {
+            broadphase.0[layer.index()].insert(entity, hitbox.aabb(transform));
+        }
+    }
+}
+
+fn detect(
+    mut events: EventWriter<CollisionEvent>,
+    broadphase: Res<Broadphase>,
+    query: Query<(Entity, &CollisionLayers, &Hitbox, &Transform)>,
+) {
+    for (entity, layers, hitbox, transform) in &query {
+        if layers.mask == Layer::NONE { continue }
+        let aabb = hitbox.aabb(transform);
+
+        for layer in layers.mask.iter() {
+            for other in broadphase.0[layer.index()].query(aabb) {
+                if other == entity { continue }
+                let Ok((_, _, other_hitbox, other_transform)) = query.get(other) else { continue };
+                if !hitbox.intersects(transform, other_hitbox, other_transform) { continue }
+                // trace!("detect: {:?} {:?} {:?}", entity, other, layer);
+                events.send(CollisionEvent { entity, other, layer, });
+            }
+        }
+    }
+}
+
+pub struct CollisionPlugin;
+
+impl Plugin for CollisionPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<CollisionEvent>()
+            .init_resource::<Broadphase>()
+            .add_systems(FixedUpdate, (
+                rebuild,
+                detect,
+            ).chain().in_set(TickSet::Broadphase))
+        ;
+    }
+}
@@ -0,0 +1,184 @@
+use bevy::{
+    prelude::*,
+    math::bounding::Aabb2d,
+};
+use serde::Deserialize;
+
+const EPSILON: f32 = 1e-6;
+
+// in the entity's own units, so the sprite's rotation and scale carry over
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum Shape {
+    Circle { radius: f32 },
+    // a segment from -half_segment to half_segment, grown by the radius
+    Capsule { radius: f32, half_segment: Vec2 },
+    Rect { half_size: Vec2 },
+}
+
+#[derive(Component, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Hitbox {
+    pub shape: Shape,
+    #[serde(default)]
+    pub offset: Vec2,
+}
+
+// a hitbox placed in the world
+enum Collider {
+    Round { a: Vec2, b: Vec2, radius: f32 },
+    Oriented { center: Vec2, axes: [Vec2; 2], half_size: Vec2 },
+}
+
+impl Hitbox {
+    pub const fn circle(radius: f32) -> Self {
+        Self { shape: Shape::Circle { radius }, offset: Vec2::ZERO, }
+    }
+
+    pub const fn capsule(radius: f32, half_segment: Vec2) -> Self {
+        Self { shape: Shape::Capsule { radius, half_segment }, offset: Vec2::ZERO, }
+    }
+
+    pub const fn rect(size: Vec2) -> Self {
+        Self { shape: Shape::Rect { half_size: Vec2::new(size.x / 2.0, size.y / 2.0) }, offset: Vec2::ZERO, }
+    }
+
+    pub const fn with_offset(mut self, offset: Vec2) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn aabb(&self, transform: &Transform) -> Aabb2d {
+        self.collider(transform).aabb()
+    }
+
+    pub fn intersects(&self, transform: &Transform, other: &Hitbox, other_transform: &Transform) -> bool {
+        self.collider(transform).intersects(&other.collider(other_transform))
+    }
+
+    fn collider(&self, transform: &Transform) -> Collider {
+        let axis_x = (transform.rotation * Vec3::X).xy().normalize_or(Vec2::X);
+        let axes = [axis_x, axis_x.perp()];
+        let scale = transform.scale.xy().abs();
+        let to_world = |local: Vec2| {
+            let local = (self.offset + local) * scale;
+            transform.translation.xy() + axes[0] * local.x + axes[1] * local.y
+        };
+        // circles stay round, so an uneven scale grows them by the larger side
+        let round_scale = scale.max_element();
+
+        match self.shape {
+            Shape::Circle { radius } => Collider::Round {
+                a: to_world(Vec2::ZERO),
+                b: to_world(Vec2::ZERO),
+                radius: radius * round_scale,
+            },
+            Shape::Capsule { radius, half_segment } => Collider::Round {
+                a: to_world(-half_segment),
+                b: to_world(half_segment),
+                radius: radius * round_scale,
+            },
+            Shape::Rect { half_size } => Collider::Oriented {
+                center: to_world(Vec2::ZERO),
+                axes,
+                half_size: half_size * scale,
+            },
+        }
+    }
+}
+
+impl Default for Hitbox {
+    fn default() -> Self { Self::rect(Vec2::ZERO) }
+}
+
+impl Collider {
+    fn aabb(&self) -> Aabb2d {
+        match *self {
+            Collider::Round { a, b, radius } => Aabb2d {
+                min: a.min(b) - radius,
+                max: a.max(b) + radius,
+            },
+            Collider::Oriented { center, axes, half_size } => {
+                let extent = (axes[0] * half_size.x).abs() + (axes[1] * half_size.y).abs();
+                Aabb2d::new(center, extent)
+            }
+        }
+    }
+
+    fn intersects(&self, other: &Collider) -> bool {
+        match (self, other) {
+            (
+                Collider::Round { a, b, radius },
+                Collider::Round { a: other_a, b: other_b, radius: other_radius },
+            ) => segment_distance_squared(*a, *b, *other_a, *other_b) <= (radius + other_radius).powi(2),
+            (Collider::Round { a, b, radius }, oriented @ Collider::Oriented { .. })
+            | (oriented @ Collider::Oriented { .. }, Collider::Round { a, b, radius }) => {
+                oriented.distance_squared_to_segment(*a, *b) <= radius.powi(2)
+            }
+            (
+                Collider::Oriented { center, axes, half_size },
+                Collider::Oriented { center: other_center, axes: other_axes, half_size: other_half_size },
+            ) => {
+                // separating axis test over the sides of both boxes
+                let offset = *other_center - *center;
+                let reach = |axis: Vec2, axes: &[Vec2; 2], half_size: Vec2| {
+                    axes[0].dot(axis).abs() * half_size.x + axes[1].dot(axis).abs() * half_size.y
+                };
+
+                axes.iter().chain(other_axes).all(|axis| {
+                    offset.dot(*axis).abs() <= reach(*axis, axes, *half_size) + reach(*axis, other_axes, *other_half_size)
+                })
+            }
+        }
+    }
+
+    fn distance_squared_to_segment(&self, a: Vec2, b: Vec2) -> f32 {
+        let Collider::Oriented { center, axes, half_size } = *self else { return f32::INFINITY };
+        // in the box's own frame the box is axis aligned around the origin
+        let local = |point: Vec2| Vec2::new((point - center).dot(axes[0]), (point - center).dot(axes[1]));
+        let (a, b) = (local(a), local(b));
+        let corners = [
+            Vec2::new(-half_size.x, -half_size.y),
+            Vec2::new(half_size.x, -half_size.y),
+            Vec2::new(half_size.x, half_size.y),
+            Vec2::new(-half_size.x, half_size.y),
+        ];
+        let inside = |point: Vec2| (point.abs() - half_size).max(Vec2::ZERO).length_squared();
+
+        // either an end is inside or closest, or the segment passes closest to one of the sides
+        (0..corners.len())
+            .map(|i| segment_distance_squared(a, b, corners[i], corners[(i + 1) % corners.len()]))
+            .fold(inside(a).min(inside(b)), f32::min)
+    }
+}
+
+// closest distance between the segments p1-q1 and p2-q2
+fn segment_distance_squared(p1: Vec2, q1: Vec2, p2: Vec2, q2: Vec2) -> f32 {
+    let (d1, d2, r) = (q1 - p1, q2 - p2, p1 - p2);
+    let (a, e, f) = (d1.length_squared(), d2.length_squared(), d2.dot(r));
+
+    let (s, t) = if a <= EPSILON && e <= EPSILON {
+        (0.0, 0.0)
+    } else if a <= EPSILON {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = d1.dot(r);
+
+        if e <= EPSILON {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = d1.dot(d2);
+            let denominator = a * e - b * b;
+            // parallel segments pick any point and let the clamping below fix it
+            let s = if denominator > EPSILON { ((b * f - c * e) / denominator).clamp(0.0, 1.0) } else { 0.0 };
+            let t = (b * s + f) / e;
+
+            if t < 0.0 {
+                ((-c / a).clamp(0.0, 1.0), 0.0)
+            } else if t > 1.0 {
+                (((b - c) / a).clamp(0.0, 1.0), 1.0)
+            } else {
+                (s, t)
+            }
+        }
+    };
+    ((p1 + d1 * s) - (p2 + d2 * t)).length_squared()
+}
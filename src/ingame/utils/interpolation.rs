@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+
+use crate::MyCamera;
+use crate::ingame::player::Player;
+use crate::ingame::utils::velocity::Velocity;
+
+#[derive(Component)]
+pub struct Interpolated {
+    previous: Vec3,
+    current: Vec3,
+    rendered: Vec3,
+}
+
+impl Interpolated {
+    fn new(translation: Vec3) -> Self {
+        Self { previous: translation, current: translation, rendered: translation, }
+    }
+
+    // a translation set outside the simulation (e.g. a reset on a state change) is a teleport
+    fn sync(&mut self, translation: Vec3) {
+        if translation == self.rendered { return }
+        *self = Self::new(translation);
+    }
+}
+
+fn restore(
+    mut query: Query<(&mut Transform, &mut Interpolated)>,
+) {
+    for (mut transform, mut interpolated) in &mut query {
+        interpolated.sync(transform.translation);
+        interpolated.previous = interpolated.current;
+        transform.translation = interpolated.current;
+    }
+}
+
+fn record(
+    mut commands: Commands,
+    mut query: Query<(&Transform, &mut Interpolated)>,
+    new_query: Query<(Entity, &Transform), (Or<(With<Velocity>, With<Player>, With<MyCamera>)>, Without<Interpolated>)>,
+) {
+    for (transform, mut interpolated) in &mut query {
+        interpolated.current = transform.translation;
+    }
+    for (entity, transform) in &new_query {
+        commands.entity(entity).insert(Interpolated::new(transform.translation));
+    }
+}
+
+fn interpolate(
+    mut query: Query<(&mut Transform, &mut Interpolated)>,
+    time: Res<Time<Fixed>>,
+) {
+    let alpha = time.overstep_fraction();
+
+    for (mut transform, mut interpolated) in &mut query {
+        interpolated.sync(transform.translation);
+        transform.translation = interpolated.previous.lerp(interpolated.current, alpha);
+        interpolated.rendered = transform.translation;
+    }
+}
+
+pub struct InterpolationPlugin;
+
+impl Plugin for InterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(FixedFirst, restore)
+            .add_systems(FixedLast, record)
+            .add_systems(RunFixedMainLoop, interpolate.in_set(RunFixedMainLoopSystem::AfterFixedMainLoop))
+        ;
+    }
+}
@@ -4,8 +4,19 @@ pub mod prelude;
 
 mod animation_config;
 mod bullet;
+mod collision;
+mod hitbox;
+mod interpolation;
+mod pool;
+mod spatial_grid;
 mod velocity;
 
+pub use pool::{
+    POOL_ACTIVE,
+    POOL_FREE,
+};
+pub use spatial_grid::SpatialGrid;
+
 pub struct UtilsPlugin;
 
 impl Plugin for UtilsPlugin {
@@ -13,6 +24,9 @@ impl Plugin for UtilsPlugin {
         app
             .add_plugins(animation_config::AnimationConfigPlugin)
             .add_plugins(bullet::BulletPlugin)
+            .add_plugins(collision::CollisionPlugin)
+            .add_plugins(interpolation::InterpolationPlugin)
+            .add_plugins(pool::PoolPlugin)
             .add_plugins(velocity::VelocityPlugin)
         ;
     }
@@ -0,0 +1,115 @@
+use bevy::{
+    prelude::*,
+    diagnostic::{
+        Diagnostic,
+        DiagnosticPath,
+        Diagnostics,
+        RegisterDiagnostic,
+    },
+    render::{
+        sync_world::SyncToRenderWorld,
+        view::{
+            InheritedVisibility,
+            ViewVisibility,
+        },
+    },
+    utils::HashMap,
+};
+
+use crate::AppState;
+
+pub const POOL_ACTIVE: DiagnosticPath = DiagnosticPath::const_new("pool_active");
+pub const POOL_FREE: DiagnosticPath = DiagnosticPath::const_new("pool_free");
+
+// what a released entity keeps while it waits, hidden, to be handed out again
+type Kept = (
+    Pooled,
+    Sprite,
+    Transform,
+    GlobalTransform,
+    Visibility,
+    InheritedVisibility,
+    ViewVisibility,
+    SyncToRenderWorld,
+);
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Pooled {
+    Bullet,
+    Explosion,
+}
+
+#[derive(Component)]
+struct Free;
+
+#[derive(Resource, Default)]
+pub struct Pool {
+    free: HashMap<Pooled, Vec<Entity>>,
+}
+
+pub trait ReleaseCommands {
+    // returns a pooled entity to its pool, anything else is despawned
+    fn release(&mut self, entity: Entity);
+}
+
+impl Pool {
+    pub fn spawn(&mut self, commands: &mut Commands, kind: Pooled, bundle: impl Bundle) -> Entity {
+        match self.free.get_mut(&kind).and_then(Vec::pop) {
+            Some(entity) => {
+                commands.entity(entity).remove::<Free>().insert((bundle, Visibility::Inherited));
+                entity
+            }
+            None => commands.spawn((bundle, kind)).id(),
+        }
+    }
+}
+
+impl ReleaseCommands for Commands<'_, '_> {
+    fn release(&mut self, entity: Entity) {
+        self.queue(move |world: &mut World| {
+            let Ok(mut entity_mut) = world.get_entity_mut(entity) else { return };
+            let Some(kind) = entity_mut.get::<Pooled>().copied() else {
+                entity_mut.despawn();
+                return
+            };
+            // released twice in the same tick, e.g. by a hit and the bomb
+            if entity_mut.contains::<Free>() { return }
+
+            entity_mut.retain::<Kept>().insert((Free, Visibility::Hidden));
+            world.resource_mut::<Pool>().free.entry(kind).or_default().push(entity);
+        });
+    }
+}
+
+fn measure(
+    mut diagnostics: Diagnostics,
+    active_query: Query<(), (With<Pooled>, Without<Free>)>,
+    free_query: Query<(), With<Free>>,
+) {
+    diagnostics.add_measurement(&POOL_ACTIVE, || active_query.iter().count() as f64);
+    diagnostics.add_measurement(&POOL_FREE, || free_query.iter().count() as f64);
+}
+
+fn all_despawn(
+    mut commands: Commands,
+    mut pool: ResMut<Pool>,
+    query: Query<Entity, With<Pooled>>,
+) {
+    // debug!("all_despawn");
+    pool.free.clear();
+    for entity in &query { commands.entity(entity).despawn() }
+}
+
+pub struct PoolPlugin;
+
+impl Plugin for PoolPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Pool>()
+            .register_diagnostic(Diagnostic::new(POOL_ACTIVE))
+            .register_diagnostic(Diagnostic::new(POOL_FREE))
+            .add_systems(Last, measure)
+            .add_systems(OnExit(AppState::Ingame), all_despawn)
+        ;
+    }
+}
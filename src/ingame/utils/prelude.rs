@@ -5,7 +5,24 @@ pub use crate::ingame::utils::animation_config::{
 
 pub use crate::ingame::utils::bullet::{
     Bullet,
-    Shooter,
+    Refund,
+};
+
+pub use crate::ingame::utils::collision::{
+    CollisionEvent,
+    CollisionLayers,
+    Layer,
+};
+
+pub use crate::ingame::utils::hitbox::{
+    Hitbox,
+    Shape,
+};
+
+pub use crate::ingame::utils::pool::{
+    Pool,
+    Pooled,
+    ReleaseCommands,
 };
 
 pub use crate::ingame::utils::velocity::Velocity;
@@ -0,0 +1,66 @@
+use bevy::{
+    prelude::*,
+    math::bounding::{Aabb2d, IntersectsVolume},
+};
+
+use crate::ingame::GRID_SIZE;
+
+// cells wrap around every TABLE_SIZE cells, which is wider than the screen
+const TABLE_SIZE: i32 = 64;
+
+// boxes bucketed into GRID_SIZE cells
+pub struct SpatialGrid {
+    // each entry keeps the first cell it covers
+    cells: Vec<Vec<(Entity, Aabb2d, IVec2)>>,
+}
+
+impl SpatialGrid {
+    pub fn clear(&mut self) {
+        // the cells keep their allocations for the next tick
+        for entries in &mut self.cells { entries.clear() }
+    }
+
+    pub fn insert(&mut self, entity: Entity, aabb: Aabb2d) {
+        let min = cell(aabb.min);
+        // a box wider than the table would otherwise land in the same cell twice
+        let max = cell(aabb.max).min(min + IVec2::splat(TABLE_SIZE - 1));
+
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                self.cells[index(IVec2::new(x, y))].push((entity, aabb, min));
+            }
+        }
+    }
+
+    // every entity whose box overlaps the given one, each reported once
+    pub fn query(&self, aabb: Aabb2d) -> impl Iterator<Item = Entity> + '_ {
+        let (min, max) = (cell(aabb.min), cell(aabb.max));
+
+        (min.y..=max.y)
+            .flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+            .flat_map(move |position| {
+                self.cells[index(position)].iter().filter_map(move |(entity, other, other_min)| {
+                    // an entity spanning several cells is only reported from the first shared one,
+                    // which also skips the far away boxes sharing a wrapped cell
+                    let first = position == min.max(*other_min);
+                    (first && aabb.intersects(other)).then_some(*entity)
+                })
+            })
+    }
+}
+
+impl Default for SpatialGrid {
+    fn default() -> Self {
+        Self { cells: vec![Vec::new(); (TABLE_SIZE * TABLE_SIZE) as usize], }
+    }
+}
+
+fn cell(position: Vec2) -> IVec2 {
+    (position / GRID_SIZE).floor().as_ivec2()
+}
+
+fn index(cell: IVec2) -> usize {
+    let wrapped = cell.rem_euclid(IVec2::splat(TABLE_SIZE));
+
+    (wrapped.y * TABLE_SIZE + wrapped.x) as usize
+}
@@ -1,19 +1,20 @@
 use bevy::prelude::*;
 
-use crate::AppState;
-use crate::ingame::camera::SPEED as CAMERA_SPEED;
+use crate::ingame::TickSet;
+use crate::ingame::camera::ScrollLock;
 
-#[derive(Component, Deref, DerefMut, Debug)]
+#[derive(Component, Deref, DerefMut, Default, Debug)]
 pub struct Velocity(pub Vec2);
 
 fn apply_velocity(
     mut query: Query<(&mut Transform, &Velocity), With<Velocity>>,
-    time_step: Res<Time<Fixed>>,
+    time: Res<Time>,
+    scroll: Res<ScrollLock>,
 ) {
     for (mut transform, velocity) in &mut query {
-        transform.translation.x += velocity.x * time_step.delta().as_secs_f32();
-        transform.translation.y += velocity.y * time_step.delta().as_secs_f32();
-        transform.translation.y += CAMERA_SPEED;
+        transform.translation.x += velocity.x * time.delta_secs();
+        transform.translation.y += velocity.y * time.delta_secs();
+        transform.translation.y += scroll.speed() * time.delta_secs();
     }
 }
 
@@ -22,7 +23,7 @@ pub struct VelocityPlugin;
 impl Plugin for VelocityPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(Update, apply_velocity.run_if(in_state(AppState::Ingame)))
+            .add_systems(FixedUpdate, apply_velocity.in_set(TickSet::Movement))
         ;
     }
 }
@@ -0,0 +1,265 @@
+#![allow(clippy::type_complexity, clippy::too_many_arguments)]
+
+use bevy::prelude::*;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+pub mod headless;
+
+pub mod action;
+pub mod atlas;
+pub mod settings;
+pub mod locale;
+pub mod loading;
+pub mod music;
+
+pub mod background;
+pub mod mainmenu;
+pub mod ingame;
+pub mod gameover;
+pub mod highscore;
+pub mod replay;
+pub mod options;
+
+mod ron_asset;
+mod storage;
+
+// the window title until the string table is loaded
+pub const GAMETITLE: &str = "いっとく2Dシューティングゲーム";
+pub const WINDOW_SIZE: Vec2 = Vec2::new(640.0, 480.0);
+pub const PATH_FONT: &str = "fonts/misaki_gothic.ttf";
+pub const INVULNERABLE_DURATION: f32 = 1.5;
+
+#[derive(States, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AppState {
+    Loading,
+    Mainmenu,
+    Ingame,
+    StageClear,
+    Gameover,
+    Restart,
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct GameConfig {
+    pub window_size: Vec2,
+    // used for every language instead of the font in the string table
+    pub font_path: Option<String>,
+    pub invulnerable_duration: f32,
+    pub persistent: bool,
+    pub seed: Option<u64>,
+}
+
+#[derive(Resource)]
+pub struct Score {
+    kills: Vec<Kills>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Kills {
+    pub name: String,
+    pub count: usize,
+    pub score: usize,
+}
+
+#[derive(Component)]
+pub struct MyCamera;
+
+impl Score {
+    pub fn new() -> Self {
+        Self { kills: Vec::new(), }
+    }
+
+    pub fn add(&mut self, name: &str, score: usize) {
+        match self.kills.iter_mut().find(|kills| kills.name == name) {
+            Some(kills) => {
+                kills.count += 1;
+                kills.score += score;
+            }
+            None => self.kills.push(Kills { name: name.to_string(), count: 1, score, }),
+        }
+    }
+
+    pub fn kills(&self) -> &[Kills] { &self.kills }
+
+    pub fn count(&self, name: &str) -> usize {
+        self.kills.iter().find(|kills| kills.name == name).map_or(0, |kills| kills.count)
+    }
+
+    pub fn sum_of(&self, name: &str) -> usize {
+        self.kills.iter().find(|kills| kills.name == name).map_or(0, |kills| kills.score)
+    }
+
+    pub fn sum(&self) -> usize { self.kills.iter().map(|kills| kills.score).sum() }
+
+    fn reset() -> Self { Self::new() }
+}
+
+impl Default for Score {
+    fn default() -> Self { Self::new() }
+}
+
+pub struct GamePlugin {
+    window_size: Vec2,
+    initial_state: AppState,
+    font_path: Option<String>,
+    invulnerable_duration: f32,
+    persistent: bool,
+    seed: Option<u64>,
+    replay: Option<replay::Replay>,
+    settings: settings::Settings,
+    background: bool,
+    mainmenu: bool,
+    ingame: bool,
+    gameover: bool,
+}
+
+impl GamePlugin {
+    pub fn new() -> Self {
+        Self {
+            window_size: WINDOW_SIZE,
+            initial_state: AppState::Mainmenu,
+            font_path: None,
+            invulnerable_duration: INVULNERABLE_DURATION,
+            persistent: true,
+            seed: None,
+            replay: None,
+            settings: settings::Settings::default(),
+            background: true,
+            mainmenu: true,
+            ingame: true,
+            gameover: true,
+        }
+    }
+
+    pub fn window_size(mut self, window_size: Vec2) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn initial_state(mut self, initial_state: AppState) -> Self {
+        self.initial_state = initial_state;
+        self
+    }
+
+    pub fn font_path(mut self, font_path: impl Into<String>) -> Self {
+        self.font_path = Some(font_path.into());
+        self
+    }
+
+    pub fn invulnerable_duration(mut self, seconds: f32) -> Self {
+        self.invulnerable_duration = seconds;
+        self
+    }
+
+    pub fn persistent(mut self, enabled: bool) -> Self {
+        self.persistent = enabled;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn replay(mut self, replay: replay::Replay) -> Self {
+        self.replay = Some(replay);
+        self
+    }
+
+    pub fn settings(mut self, settings: settings::Settings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    pub fn background(mut self, enabled: bool) -> Self {
+        self.background = enabled;
+        self
+    }
+
+    pub fn mainmenu(mut self, enabled: bool) -> Self {
+        self.mainmenu = enabled;
+        self
+    }
+
+    pub fn ingame(mut self, enabled: bool) -> Self {
+        self.ingame = enabled;
+        self
+    }
+
+    pub fn gameover(mut self, enabled: bool) -> Self {
+        self.gameover = enabled;
+        self
+    }
+
+    pub fn config(&self) -> GameConfig {
+        GameConfig {
+            window_size: self.window_size,
+            font_path: self.font_path.clone(),
+            invulnerable_duration: self.invulnerable_duration,
+            persistent: self.persistent,
+            seed: self.seed,
+        }
+    }
+
+    pub fn window_plugin(&self) -> WindowPlugin {
+        WindowPlugin {
+            primary_window: Some(Window {
+                resolution: self.settings.window_resolution(self.window_size),
+                mode: self.settings.window_mode(),
+                canvas: Some("#bevy".into()),
+                title: GAMETITLE.to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for GamePlugin {
+    fn default() -> Self { Self::new() }
+}
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_state(AppState::Loading)
+            .insert_resource(loading::LoadingTarget(self.initial_state.clone()))
+            .insert_resource(self.config())
+            .insert_resource(self.settings.clone())
+            .insert_resource(Score::new())
+            .insert_resource(Time::<Fixed>::from_seconds(1.0 / 60.0))
+            .add_systems(Startup, setup)
+            .add_systems(OnEnter(AppState::Restart), restart)
+            .add_plugins(action::ActionPlugin)
+            .add_plugins(atlas::AtlasPlugin)
+            .add_plugins(settings::SettingsPlugin)
+            .add_plugins(locale::LocalePlugin)
+            .add_plugins(loading::LoadingPlugin)
+            .add_plugins(music::MusicPlugin)
+            .add_plugins(options::OptionsPlugin)
+            .add_plugins(highscore::HighscorePlugin)
+        ;
+        if self.background { app.add_plugins(background::BackgroundPlugin); }
+        if self.mainmenu { app.add_plugins(mainmenu::MainmenuPlugin); }
+        if let Some(replay) = &self.replay { app.insert_resource(replay::ReplayState::playback(replay.clone())); }
+        if self.ingame { app.add_plugins((ingame::IngamePlugin, replay::ReplayPlugin)); }
+        if self.gameover { app.add_plugins(gameover::GameoverPlugin); }
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+) {
+    // debug!("setup camera");
+    commands.spawn((Camera2d, MyCamera));
+}
+
+fn restart(
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    // trace!("AppState Restart -> Ingame");
+    next_state.set(AppState::Ingame);
+}
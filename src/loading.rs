@@ -0,0 +1,274 @@
+use bevy::{
+    prelude::*,
+    asset::{
+        AssetLoadError,
+        LoadState,
+        UntypedAssetId,
+        io::AssetReaderError,
+    },
+    audio::AudioSource,
+    sprite::Anchor,
+    utils::HashMap,
+};
+
+use crate::{
+    AppState,
+    GameConfig,
+};
+use crate::locale::{
+    Locale,
+    Locales,
+};
+use crate::music::MusicTracks;
+use crate::ingame::enemy::{
+    EnemyDefinition,
+    EnemyDefinitions,
+};
+use crate::ingame::stage::StageDefinitions;
+
+const PATH_BACKGROUND: &str = "ittoku-2dshooting-game/background.png";
+const PATH_PLAYER_SHIP: &str = "ittoku-2dshooting-game/player-ship.png";
+const PATH_PLAYER_BULLET: &str = "ittoku-2dshooting-game/player-bullet.png";
+const PATH_FIGHTER_BULLET: &str = "ittoku-2dshooting-game/fighter-bullet.png";
+const PATH_TORPEDO_BULLET: &str = "ittoku-2dshooting-game/torpedo-bullet.png";
+const PATH_HEART: &str = "images/hp-heart.png";
+const PATH_SOUND_SHOOT: &str = "sounds/battle-shooting-1.ogg";
+const PATH_SOUND_HIT: &str = "sounds/battle-shooting-hit.ogg";
+// the string tables are still loading, so the loading screen is not translated
+const LOADING_TEXT: &str = "NOW LOADING";
+const MISSING_TEXT: &str = "missing";
+const TEXT_SIZE: f32 = 16.0;
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const ERROR_SIZE: f32 = 12.0;
+const ERROR_COLOR: Color = Color::srgb(0.9, 0.3, 0.3);
+const ERROR_ROW: f32 = 16.0;
+const BAR_SIZE: Vec2 = Vec2::new(320.0, 8.0);
+const BAR_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const BAR_BACKGROUND_COLOR: Color = Color::srgb(0.3, 0.3, 0.3);
+
+// every sprite sheet, font and sound, loaded before the first state that uses them
+#[derive(Resource)]
+pub struct GameAssets {
+    pub background: Handle<Image>,
+    pub player_ship: Handle<Image>,
+    pub player_bullet: Handle<Image>,
+    pub fighter_bullet: Handle<Image>,
+    pub torpedo_bullet: Handle<Image>,
+    pub heart: Handle<Image>,
+    pub shoot_sound: Handle<AudioSource>,
+    pub hit_sound: Handle<AudioSource>,
+    // named by the enemy definitions and string tables, kept here so they stay loaded
+    sprites: HashMap<String, Handle<Image>>,
+    fonts: HashMap<String, Handle<Font>>,
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct LoadingProgress {
+    pub loaded: usize,
+    pub total: usize,
+    pub missing: Vec<String>,
+}
+
+// where to go once everything is in
+#[derive(Resource)]
+pub struct LoadingTarget(pub AppState);
+
+#[derive(Component)]
+struct Loading;
+
+#[derive(Component)]
+struct ProgressBar;
+
+impl GameAssets {
+    fn load(asset_server: &AssetServer) -> Self {
+        Self {
+            background: asset_server.load(PATH_BACKGROUND),
+            player_ship: asset_server.load(PATH_PLAYER_SHIP),
+            player_bullet: asset_server.load(PATH_PLAYER_BULLET),
+            fighter_bullet: asset_server.load(PATH_FIGHTER_BULLET),
+            torpedo_bullet: asset_server.load(PATH_TORPEDO_BULLET),
+            heart: asset_server.load(PATH_HEART),
+            shoot_sound: asset_server.load(PATH_SOUND_SHOOT),
+            hit_sound: asset_server.load(PATH_SOUND_HIT),
+            sprites: HashMap::new(),
+            fonts: HashMap::new(),
+        }
+    }
+
+    fn ids(&self) -> impl Iterator<Item = UntypedAssetId> + '_ {
+        [
+            self.background.id().untyped(),
+            self.player_ship.id().untyped(),
+            self.player_bullet.id().untyped(),
+            self.fighter_bullet.id().untyped(),
+            self.torpedo_bullet.id().untyped(),
+            self.heart.id().untyped(),
+            self.shoot_sound.id().untyped(),
+            self.hit_sound.id().untyped(),
+        ]
+        .into_iter()
+        .chain(self.sprites.values().map(|handle| handle.id().untyped()))
+        .chain(self.fonts.values().map(|handle| handle.id().untyped()))
+    }
+}
+
+impl LoadingProgress {
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 { return 0.0 }
+        self.loaded as f32 / self.total as f32
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    // debug!("setup");
+    commands.insert_resource(GameAssets::load(&asset_server));
+}
+
+fn spawn_screen(
+    mut commands: Commands,
+) {
+    // debug!("spawn_screen");
+    commands.spawn((
+        Text2d::new(LOADING_TEXT),
+        TextFont {
+            font_size: TEXT_SIZE,
+            ..Default::default()
+        },
+        TextColor(TEXT_COLOR),
+        Transform::from_xyz(0.0, BAR_SIZE.y * 2.0, 0.0),
+        Loading,
+    ));
+    commands.spawn((
+        Sprite {
+            color: BAR_BACKGROUND_COLOR,
+            custom_size: Some(BAR_SIZE),
+            ..Default::default()
+        },
+        Transform::from_xyz(0.0, 0.0, 0.0),
+        Loading,
+    ));
+    commands.spawn((
+        Sprite {
+            color: BAR_COLOR,
+            custom_size: Some(Vec2::new(0.0, BAR_SIZE.y)),
+            anchor: Anchor::CenterLeft,
+            ..Default::default()
+        },
+        Transform::from_xyz(-BAR_SIZE.x / 2.0, 0.0, 1.0),
+        ProgressBar,
+        Loading,
+    ));
+}
+
+fn progress(
+    mut commands: Commands,
+    mut game_assets: ResMut<GameAssets>,
+    mut progress: ResMut<LoadingProgress>,
+    mut next_state: ResMut<NextState<AppState>>,
+    asset_server: Res<AssetServer>,
+    target: Res<LoadingTarget>,
+    config: Res<GameConfig>,
+    locales: Res<Locales>,
+    locale_assets: Res<Assets<Locale>>,
+    music_tracks: Res<MusicTracks>,
+    enemy_definitions: Option<Res<EnemyDefinitions>>,
+    enemy_definition_assets: Option<Res<Assets<EnemyDefinition>>>,
+    stage_definitions: Option<Res<StageDefinitions>>,
+) {
+    // the files the definitions point at join in as soon as the definitions themselves are read
+    let game_assets = &mut *game_assets;
+    for (_, definition) in enemy_definition_assets.iter().flat_map(|assets| assets.iter()) {
+        for path in [&definition.sprite, &definition.explosion] {
+            game_assets.sprites.entry(path.clone()).or_insert_with(|| asset_server.load(path));
+        }
+    }
+    let fonts = locale_assets.iter().filter_map(|(_, locale)| locale.font());
+    for path in config.font_path.as_deref().into_iter().chain(fonts) {
+        game_assets.fonts.entry(path.to_string()).or_insert_with(|| asset_server.load(path));
+    }
+
+    let ids = game_assets.ids()
+        .chain(locales.ids())
+        .chain(music_tracks.ids())
+        .chain(enemy_definitions.iter().flat_map(|definitions| definitions.ids()))
+        .chain(stage_definitions.iter().flat_map(|definitions| definitions.ids()))
+        .collect::<Vec<_>>();
+    progress.total = ids.len();
+    progress.loaded = 0;
+
+    for id in ids {
+        match asset_server.get_load_state(id) {
+            Some(LoadState::Loaded) => progress.loaded += 1,
+            Some(LoadState::Failed(error)) => match &*error {
+                // a build without the loader (e.g. headless) never draws or plays these anyway
+                AssetLoadError::MissingAssetLoader { .. }
+                | AssetLoadError::MissingAssetLoaderForExtension(_)
+                | AssetLoadError::MissingAssetLoaderForTypeName(_)
+                | AssetLoadError::MissingAssetLoaderForTypeIdError(_) => progress.loaded += 1,
+                error => {
+                    let message = match error {
+                        AssetLoadError::AssetReaderError(AssetReaderError::NotFound(path)) => {
+                            format!("{}: {}", MISSING_TEXT, path.display())
+                        }
+                        error => error.to_string(),
+                    };
+                    if progress.missing.contains(&message) { continue }
+                    error!("{}", message);
+                    // listed on screen, since the game would otherwise draw nothing in its place
+                    commands.spawn((
+                        Text2d::new(message.clone()),
+                        TextFont {
+                            font_size: ERROR_SIZE,
+                            ..Default::default()
+                        },
+                        TextColor(ERROR_COLOR),
+                        Transform::from_xyz(0.0, -ERROR_ROW * (progress.missing.len() + 2) as f32, 0.0),
+                        Loading,
+                    ));
+                    progress.missing.push(message);
+                }
+            },
+            _ => {}
+        }
+    }
+
+    if progress.loaded < progress.total || !progress.missing.is_empty() { return }
+    // trace!("AppState Loading -> {:?}", target.0);
+    next_state.set(target.0.clone());
+}
+
+fn update_bar(
+    progress: Res<LoadingProgress>,
+    mut query: Query<&mut Sprite, With<ProgressBar>>,
+) {
+    let Ok(mut sprite) = query.get_single_mut() else { return };
+    sprite.custom_size = Some(Vec2::new(BAR_SIZE.x * progress.fraction(), BAR_SIZE.y));
+}
+
+fn all_despawn(
+    mut commands: Commands,
+    query: Query<Entity, With<Loading>>,
+) {
+    // debug!("all_despawn");
+    for entity in &query { commands.entity(entity).despawn() }
+}
+
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<LoadingProgress>()
+            .add_systems(PreStartup, setup)
+            .add_systems(OnEnter(AppState::Loading), spawn_screen)
+            .add_systems(Update, (
+                progress,
+                update_bar,
+            ).chain().run_if(in_state(AppState::Loading)))
+            .add_systems(OnExit(AppState::Loading), all_despawn)
+        ;
+    }
+}
@@ -0,0 +1,147 @@
+use bevy::{
+    prelude::*,
+    asset::UntypedAssetId,
+    window::PrimaryWindow,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::GameConfig;
+use crate::ron_asset::{
+    RonAsset,
+    RonAssetLoader,
+};
+use crate::settings::{
+    Language,
+    Settings,
+};
+
+const PATHS: [(Language, &str); 2] = [
+    (Language::Japanese, "locales/ja.locale.ron"),
+    (Language::English, "locales/en.locale.ron"),
+];
+const TITLE_KEY: &str = "title";
+
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct Locale {
+    // without a font the text falls back to the font built into bevy
+    #[serde(default)]
+    font: Option<String>,
+    strings: HashMap<String, String>,
+}
+
+#[derive(Resource)]
+pub struct Locales(Vec<(Language, Handle<Locale>)>);
+
+// the string table of the current language
+#[derive(Resource, Default)]
+pub struct Strings {
+    language: Option<Language>,
+    font: Handle<Font>,
+    strings: HashMap<String, String>,
+}
+
+// text that is looked up again when the language changes
+#[derive(Component, Deref)]
+pub struct Localized(pub &'static str);
+
+impl RonAsset for Locale {
+    const EXTENSIONS: &'static [&'static str] = &["locale.ron"];
+}
+
+impl Locale {
+    pub fn font(&self) -> Option<&str> { self.font.as_deref() }
+}
+
+impl Locales {
+    pub fn ids(&self) -> impl Iterator<Item = UntypedAssetId> + '_ {
+        self.0.iter().map(|(_, handle)| handle.id().untyped())
+    }
+
+    fn get(&self, language: Language) -> Option<&Handle<Locale>> {
+        self.0.iter().find(|(other, _)| *other == language).map(|(_, handle)| handle)
+    }
+}
+
+impl Strings {
+    // a missing key shows up as the key itself
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.strings.get(key).map_or(key, String::as_str)
+    }
+
+    pub fn get_or<'a>(&'a self, key: &str, fallback: &'a str) -> &'a str {
+        self.strings.get(key).map_or(fallback, String::as_str)
+    }
+
+    pub fn font(&self) -> Handle<Font> { self.font.clone() }
+
+    pub fn language(&self) -> Option<Language> { self.language }
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    // debug!("setup");
+    let handles = PATHS.iter().map(|(language, path)| (*language, asset_server.load(*path))).collect();
+    commands.insert_resource(Locales(handles));
+}
+
+fn select(
+    mut strings: ResMut<Strings>,
+    mut events: EventReader<AssetEvent<Locale>>,
+    locales: Res<Locales>,
+    locale_assets: Res<Assets<Locale>>,
+    settings: Res<Settings>,
+    config: Res<GameConfig>,
+    asset_server: Res<AssetServer>,
+) {
+    let reloaded = events.read().count() > 0;
+
+    if !reloaded && strings.language == Some(settings.language) { return }
+    let Some(locale) = locales.get(settings.language).and_then(|handle| locale_assets.get(handle)) else { return };
+    // debug!("select: {:?}", settings.language);
+    *strings = Strings {
+        language: Some(settings.language),
+        font: config.font_path.as_ref()
+            .or(locale.font.as_ref())
+            .map_or_else(Handle::default, |path| asset_server.load(path)),
+        strings: locale.strings.clone(),
+    };
+}
+
+fn localize(
+    strings: Res<Strings>,
+    mut text_query: Query<(&Localized, &mut Text)>,
+    mut text2d_query: Query<(&Localized, &mut Text2d)>,
+    mut span_query: Query<(&Localized, &mut TextSpan)>,
+    mut font_query: Query<&mut TextFont>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    // debug!("localize");
+    for (key, mut text) in &mut text_query { **text = strings.get(key).to_string() }
+    for (key, mut text) in &mut text2d_query { **text = strings.get(key).to_string() }
+    for (key, mut span) in &mut span_query { **span = strings.get(key).to_string() }
+    for mut font in &mut font_query { font.font = strings.font() }
+
+    if let Ok(mut window) = window_query.get_single_mut() {
+        window.title = strings.get(TITLE_KEY).to_string();
+    }
+}
+
+pub struct LocalePlugin;
+
+impl Plugin for LocalePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_asset::<Locale>()
+            .init_asset_loader::<RonAssetLoader<Locale>>()
+            .init_resource::<Strings>()
+            .add_systems(Startup, setup)
+            .add_systems(Update, (
+                select,
+                localize.run_if(resource_changed::<Strings>),
+            ).chain())
+        ;
+    }
+}
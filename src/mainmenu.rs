@@ -1,16 +1,36 @@
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    sprite::Anchor,
+};
 
-use crate::{
-    GAMETITLE,
-    PATH_FONT,
-    AppState,
+use crate::AppState;
+use crate::action::{
+    Actions,
+    InputAction,
+};
+use crate::highscore::HighScores;
+use crate::locale::{
+    Localized,
+    Strings,
 };
+use crate::options::OptionsState;
 
+const GAMETITLE_TEXT: &str = "title";
 const GAMETITLE_SIZE: f32 = 20.0;
 const GAMETITLE_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
-const CLICKSTART_TEXT: &str = "クリックしてスタート";
+const CLICKSTART_TEXT: &str = "mainmenu.start";
+const OPTIONS_TEXT: &str = "mainmenu.options";
+const OPTIONS_SIZE: f32 = 12.0;
 const CLICKSTART_COLOR: Color = Color::srgb(0.4, 0.4, 0.4);
-const BOARD_SIZE: Vec2 = Vec2::new(360.0, 270.0);
+const HIGHSCORE_TEXT: &str = "mainmenu.highscore";
+const HIGHSCORE_EMPTY_TEXT: &str = "mainmenu.highscore_empty";
+// enemy names are looked up under this prefix, an enemy without one shows its id
+const ENEMY_PREFIX: &str = "enemy.";
+const HIGHSCORE_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
+const HIGHSCORE_SIZE: f32 = 12.0;
+const HIGHSCORE_ROW: f32 = 16.0;
+const HIGHSCORE_PADDING: f32 = 40.0;
+const BOARD_SIZE: Vec2 = Vec2::new(480.0, 400.0);
 const BOARD_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
 const TEXT_SIZE: f32 = 16.0;
 
@@ -19,19 +39,21 @@ struct Mainmenu;
 
 fn setup(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    highscores: Res<HighScores>,
+    strings: Res<Strings>,
 ) {
     // debug!("setup");
     // game title
     let (x, y, z) = (
         0.0,
-        BOARD_SIZE.y / 4.0,
+        BOARD_SIZE.y * 3.0 / 8.0,
         0.0,
     );
     commands.spawn((
-        Text2d::new(GAMETITLE),
+        Localized(GAMETITLE_TEXT),
+        Text2d::new(strings.get(GAMETITLE_TEXT)),
         TextFont {
-            font: asset_server.load(PATH_FONT),
+            font: strings.font(),
             font_size: GAMETITLE_SIZE,
             ..Default::default()
         },
@@ -40,16 +62,53 @@ fn setup(
         Transform::from_xyz(x, y, z),
         Mainmenu,
     ));
+    // highscores, with the keys of the lines that are not records
+    let mut lines = vec![(Some(HIGHSCORE_TEXT), strings.get(HIGHSCORE_TEXT).to_string())];
+    for (i, entry) in highscores.entries().iter().enumerate() {
+        let kills = entry.kills.iter()
+            .map(|kills| {
+                let name = strings.get_or(&format!("{}{}", ENEMY_PREFIX, kills.name), &kills.name);
+                format!("{} x{}", name, kills.count)
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        lines.push((None, format!("{:>2}. {:<8} {:>7}  {}", i + 1, entry.name, entry.score, kills)));
+    }
+    if highscores.entries().is_empty() {
+        lines.push((Some(HIGHSCORE_EMPTY_TEXT), strings.get(HIGHSCORE_EMPTY_TEXT).to_string()));
+    }
+    for (i, (key, line)) in lines.into_iter().enumerate() {
+        let (x, y, z) = (
+            -BOARD_SIZE.x / 2.0 + HIGHSCORE_PADDING,
+            BOARD_SIZE.y / 4.0 - HIGHSCORE_ROW * i as f32,
+            0.0,
+        );
+        let mut entity = commands.spawn((
+            Text2d::new(line),
+            TextFont {
+                font: strings.font(),
+                font_size: HIGHSCORE_SIZE,
+                ..Default::default()
+            },
+            TextColor(HIGHSCORE_COLOR),
+            TextLayout::new_with_justify(JustifyText::Left),
+            Anchor::CenterLeft,
+            Transform::from_xyz(x, y, z),
+            Mainmenu,
+        ));
+        if let Some(key) = key { entity.insert(Localized(key)); }
+    }
     // click start
     let (x, y, z) = (
         0.0,
-        -BOARD_SIZE.y / 4.0,
+        -BOARD_SIZE.y * 3.0 / 8.0,
         0.0,
     );
    commands.spawn((
-        Text2d::new(CLICKSTART_TEXT),
+        Localized(CLICKSTART_TEXT),
+        Text2d::new(strings.get(CLICKSTART_TEXT)),
         TextFont {
-            font: asset_server.load(PATH_FONT),
+            font: strings.font(),
             font_size: TEXT_SIZE,
             ..Default::default()
         },
@@ -58,6 +117,25 @@ fn setup(
         Transform::from_xyz(x, y, z),
         Mainmenu,
     ));
+    // options
+    let (x, y, z) = (
+        0.0,
+        -BOARD_SIZE.y * 7.0 / 16.0,
+        0.0,
+    );
+    commands.spawn((
+        Localized(OPTIONS_TEXT),
+        Text2d::new(strings.get(OPTIONS_TEXT)),
+        TextFont {
+            font: strings.font(),
+            font_size: OPTIONS_SIZE,
+            ..Default::default()
+        },
+        TextColor(CLICKSTART_COLOR),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Transform::from_xyz(x, y, z),
+        Mainmenu,
+    ));
     // board
     let (x, y, z) = (
         0.0,
@@ -77,9 +155,14 @@ fn setup(
 
 fn update(
     mut next_state: ResMut<NextState<AppState>>,
-    mouse_events: Res<ButtonInput<MouseButton>>,
+    mut next_options_state: ResMut<NextState<OptionsState>>,
+    actions: Actions,
 ) {
-    if !mouse_events.just_pressed(MouseButton::Left) { return }
+    if actions.just_pressed(InputAction::Options) {
+        next_options_state.set(OptionsState::General);
+        return
+    }
+    if !actions.just_pressed(InputAction::Start) { return }
     // trace!("AppState Mainmenu -> Ingame");
     next_state.set(AppState::Ingame);
 }
@@ -98,7 +181,7 @@ impl Plugin for MainmenuPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_systems(OnEnter(AppState::Mainmenu), setup)
-            .add_systems(Update, update.run_if(in_state(AppState::Mainmenu)))
+            .add_systems(Update, update.run_if(in_state(AppState::Mainmenu).and(in_state(OptionsState::Closed))))
             .add_systems(OnExit(AppState::Mainmenu), all_despawn)
         ;
     }
@@ -0,0 +1,149 @@
+use bevy::{
+    prelude::*,
+    asset::UntypedAssetId,
+    audio::{
+        AddAudioSource,
+        AudioPlugin,
+        Volume,
+    },
+};
+
+use crate::AppState;
+use crate::ron_asset::RonAssetLoader;
+use crate::settings::Settings;
+use crate::ingame::boss::Boss;
+
+mod synth;
+
+pub use synth::Music;
+
+const PATHS: [(Track, &str); 4] = [
+    (Track::Title, "music/title.music.ron"),
+    (Track::Stage, "music/stage.music.ron"),
+    (Track::Boss, "music/boss.music.ron"),
+    (Track::Gameover, "music/gameover.music.ron"),
+];
+const CROSSFADE_SECONDS: f32 = 1.5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Track {
+    Title,
+    Stage,
+    Boss,
+    Gameover,
+}
+
+#[derive(Resource)]
+pub struct MusicTracks(Vec<(Track, Handle<Music>)>);
+
+#[derive(Component)]
+pub struct Bgm {
+    track: Track,
+    level: f32,
+    fading_out: bool,
+}
+
+impl MusicTracks {
+    pub fn ids(&self) -> impl Iterator<Item = UntypedAssetId> + '_ {
+        self.0.iter().map(|(_, handle)| handle.id().untyped())
+    }
+
+    fn get(&self, track: Track) -> Option<Handle<Music>> {
+        self.0.iter().find(|(other, _)| *other == track).map(|(_, handle)| handle.clone())
+    }
+}
+
+impl Bgm {
+    fn new(track: Track) -> Self {
+        Self { track, level: 0.0, fading_out: false, }
+    }
+
+    pub fn track(&self) -> Track { self.track }
+
+    pub fn is_fading_out(&self) -> bool { self.fading_out }
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    // debug!("setup");
+    let handles = PATHS.iter().map(|(track, path)| (*track, asset_server.load(*path))).collect();
+    commands.insert_resource(MusicTracks(handles));
+}
+
+fn select_track(
+    mut commands: Commands,
+    mut query: Query<&mut Bgm>,
+    tracks: Res<MusicTracks>,
+    state: Res<State<AppState>>,
+    boss_query: Query<(), With<Boss>>,
+) {
+    let track = match state.get() {
+        AppState::Loading => return,
+        AppState::Mainmenu => Track::Title,
+        AppState::Gameover => Track::Gameover,
+        _ if !boss_query.is_empty() => Track::Boss,
+        _ => Track::Stage,
+    };
+    if query.iter().any(|bgm| bgm.track == track && !bgm.fading_out) { return }
+    // debug!("select_track: {:?}", track);
+
+    // a track that is still fading out fades back in instead of starting over
+    let mut resumed = false;
+    for mut bgm in &mut query {
+        bgm.fading_out = bgm.track != track || resumed;
+        resumed |= bgm.track == track;
+    }
+    if resumed { return }
+    let Some(handle) = tracks.get(track) else { return };
+
+    commands.spawn((
+        Bgm::new(track),
+        AudioPlayer(handle),
+        PlaybackSettings::DESPAWN.with_volume(Volume::new(0.0)),
+    ));
+}
+
+fn crossfade(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Bgm, Option<&AudioSink>)>,
+    settings: Res<Settings>,
+    time: Res<Time<Real>>,
+) {
+    let step = time.delta_secs() / CROSSFADE_SECONDS;
+
+    for (entity, mut bgm, sink) in &mut query {
+        let direction = if bgm.fading_out { -1.0 } else { 1.0 };
+        bgm.level = (bgm.level + step * direction).clamp(0.0, 1.0);
+
+        if bgm.fading_out && bgm.level == 0.0 {
+            commands.entity(entity).despawn();
+            continue
+        }
+        // the sink volume replaces the global volume, so the master volume is applied here
+        let Some(sink) = sink else { continue };
+        sink.set_volume(bgm.level * settings.music_volume * settings.master_volume);
+    }
+}
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        // without an audio output (e.g. headless) the tracks are still chosen, just not played
+        if app.is_plugin_added::<AudioPlugin>() {
+            app.add_audio_source::<Music>();
+        } else {
+            app.init_asset::<Music>();
+        }
+        app
+            .init_asset_loader::<RonAssetLoader<Music>>()
+            .add_systems(Startup, setup)
+            .add_systems(Update, (
+                select_track,
+                crossfade,
+            ).chain())
+        ;
+    }
+}
@@ -0,0 +1,207 @@
+use bevy::{
+    prelude::*,
+    audio::{
+        Decodable,
+        Source,
+    },
+};
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::ron_asset::RonAsset;
+
+const SAMPLE_RATE: u32 = 44_100;
+const STEPS_PER_BEAT: f32 = 2.0;
+const ATTACK_SECONDS: f32 = 0.005;
+const RELEASE_SECONDS: f32 = 0.03;
+const NOISE_DECAY: f32 = 4.0;
+
+// a looping chiptune, synthesized while it plays
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct Music {
+    tempo: f32,
+    voices: Vec<Voice>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct Voice {
+    wave: Wave,
+    volume: f32,
+    // one eighth note per token: a note such as "C4" or "F#5", "-" to hold the previous note,
+    // "." for a rest and "x" for a noise hit; "|" only separates bars
+    notes: String,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+enum Wave {
+    Square,
+    Triangle,
+    Saw,
+    Noise,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Step {
+    Note(f32),
+    Hold,
+    Rest,
+}
+
+struct VoiceState {
+    wave: Wave,
+    volume: f32,
+    steps: Vec<Step>,
+    frequency: f32,
+    phase: f32,
+    age: usize,
+    playing: bool,
+}
+
+pub struct MusicDecoder {
+    voices: Vec<VoiceState>,
+    samples_per_step: usize,
+    sample: usize,
+    step: usize,
+    noise: u32,
+}
+
+impl RonAsset for Music {
+    const EXTENSIONS: &'static [&'static str] = &["music.ron"];
+}
+
+impl Decodable for Music {
+    type DecoderItem = f32;
+    type Decoder = MusicDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        let voices = self.voices.iter()
+            .map(|voice| VoiceState {
+                wave: voice.wave,
+                volume: voice.volume,
+                steps: parse(&voice.notes),
+                frequency: 0.0,
+                phase: 0.0,
+                age: 0,
+                playing: false,
+            })
+            .collect();
+
+        MusicDecoder {
+            voices,
+            samples_per_step: (SAMPLE_RATE as f32 * 60.0 / self.tempo / STEPS_PER_BEAT) as usize,
+            sample: 0,
+            step: 0,
+            noise: 1,
+        }
+    }
+}
+
+fn parse(notes: &str) -> Vec<Step> {
+    notes.split_whitespace()
+        .filter(|token| *token != "|")
+        .map(|token| match token {
+            "-" => Step::Hold,
+            "." => Step::Rest,
+            "x" => Step::Note(0.0),
+            note => frequency(note).map_or_else(|| {
+                warn!("unknown note in music: {}", note);
+                Step::Rest
+            }, Step::Note),
+        })
+        .collect()
+}
+
+fn frequency(note: &str) -> Option<f32> {
+    let mut chars = note.chars();
+    let semitone = match chars.next()? {
+        'C' => 0,
+        'D' => 2,
+        'E' => 4,
+        'F' => 5,
+        'G' => 7,
+        'A' => 9,
+        'B' => 11,
+        _ => return None,
+    };
+    let rest = chars.as_str();
+    let (accidental, octave) = match rest.chars().next()? {
+        '#' => (1, &rest[1..]),
+        'b' => (-1, &rest[1..]),
+        _ => (0, rest),
+    };
+    let midi = 12 * (octave.parse::<i32>().ok()? + 1) + semitone + accidental;
+
+    Some(440.0 * 2.0_f32.powf((midi - 69) as f32 / 12.0))
+}
+
+impl MusicDecoder {
+    fn next_noise(&mut self) -> f32 {
+        // xorshift, so the hits sound the same on every loop
+        self.noise ^= self.noise << 13;
+        self.noise ^= self.noise >> 17;
+        self.noise ^= self.noise << 5;
+        self.noise as f32 / u32::MAX as f32 * 2.0 - 1.0
+    }
+}
+
+impl Iterator for MusicDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.sample == 0 {
+            let step = self.step;
+
+            for voice in &mut self.voices {
+                if voice.steps.is_empty() { continue }
+                match voice.steps[step % voice.steps.len()] {
+                    Step::Note(frequency) => {
+                        voice.frequency = frequency;
+                        voice.phase = 0.0;
+                        voice.age = 0;
+                        voice.playing = true;
+                    }
+                    Step::Hold => (),
+                    Step::Rest => voice.playing = false,
+                }
+            }
+        }
+        let noise = self.next_noise();
+        let remaining = (self.samples_per_step - self.sample) as f32 / SAMPLE_RATE as f32;
+        let mut output = 0.0;
+
+        for voice in &mut self.voices {
+            if !voice.playing { continue }
+            let held = voice.steps[(self.step + 1) % voice.steps.len()] == Step::Hold;
+            let age = voice.age as f32 / SAMPLE_RATE as f32;
+            let mut envelope = (age / ATTACK_SECONDS).min(1.0);
+
+            if !held { envelope *= (remaining / RELEASE_SECONDS).min(1.0) }
+            let value = match voice.wave {
+                Wave::Square => if voice.phase < 0.5 { 1.0 } else { -1.0 },
+                Wave::Triangle => 4.0 * (voice.phase - 0.5).abs() - 1.0,
+                Wave::Saw => 2.0 * voice.phase - 1.0,
+                Wave::Noise => noise * (1.0 - age * NOISE_DECAY).max(0.0),
+            };
+            output += value * envelope * voice.volume;
+            voice.phase = (voice.phase + voice.frequency / SAMPLE_RATE as f32).fract();
+            voice.age += 1;
+        }
+
+        self.sample += 1;
+        if self.sample >= self.samples_per_step {
+            self.sample = 0;
+            self.step += 1;
+        }
+        Some(output.clamp(-1.0, 1.0))
+    }
+}
+
+impl Source for MusicDecoder {
+    fn current_frame_len(&self) -> Option<usize> { None }
+
+    fn channels(&self) -> u16 { 1 }
+
+    fn sample_rate(&self) -> u32 { SAMPLE_RATE }
+
+    fn total_duration(&self) -> Option<Duration> { None }
+}
@@ -0,0 +1,234 @@
+use bevy::prelude::*;
+
+use crate::action::{
+    ACTIONS,
+    Actions,
+    Binding,
+    InputAction,
+    InputBindings,
+};
+use crate::locale::{
+    Localized,
+    Strings,
+};
+use crate::options::OptionsState;
+
+const TITLE_TEXT: &str = "bindings.title";
+const RESET_TEXT: &str = "bindings.reset";
+const BACK_TEXT: &str = "common.back";
+const WAITING_TEXT: &str = "bindings.waiting";
+const TITLE_SIZE: f32 = 24.0;
+const TEXT_SIZE: f32 = 14.0;
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const SELECTED_COLOR: Color = Color::srgb(0.9, 0.8, 0.3);
+const OVERLAY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.8);
+const ROW_GAP: f32 = 6.0;
+const CANCEL_KEYCODE: KeyCode = KeyCode::Escape;
+
+#[derive(Component)]
+struct BindingsMenu;
+
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+enum BindingsItem {
+    Action(InputAction),
+    Reset,
+    Back,
+}
+
+#[derive(Resource, Deref, DerefMut, Default)]
+struct Selected(usize);
+
+#[derive(Resource, Deref, DerefMut, Default)]
+struct Rebinding(Option<InputAction>);
+
+impl BindingsItem {
+    fn all() -> Vec<Self> {
+        ACTIONS.iter()
+            .map(|action| BindingsItem::Action(*action))
+            .chain([BindingsItem::Reset, BindingsItem::Back])
+            .collect()
+    }
+
+    fn text(&self, bindings: &InputBindings, rebinding: Option<InputAction>, strings: &Strings) -> String {
+        match self {
+            BindingsItem::Action(action) if rebinding == Some(*action) => format!("{}: {}", strings.get(action.key()), strings.get(WAITING_TEXT)),
+            BindingsItem::Action(action) => {
+                let keys = bindings.get(*action).iter()
+                    .map(Binding::text)
+                    .collect::<Vec<_>>()
+                    .join(" / ");
+                format!("{}: {}", strings.get(action.key()), keys)
+            }
+            BindingsItem::Reset => strings.get(RESET_TEXT).to_string(),
+            BindingsItem::Back => strings.get(BACK_TEXT).to_string(),
+        }
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    mut selected: ResMut<Selected>,
+    mut rebinding: ResMut<Rebinding>,
+    bindings: Res<InputBindings>,
+    strings: Res<Strings>,
+) {
+    // debug!("setup");
+    let font = strings.font();
+    **selected = 0;
+    **rebinding = None;
+
+    commands.spawn((
+        BindingsMenu,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(ROW_GAP),
+            ..Default::default()
+        },
+        BackgroundColor(OVERLAY_COLOR),
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Localized(TITLE_TEXT),
+            Text::new(strings.get(TITLE_TEXT)),
+            TextFont {
+                font: font.clone(),
+                font_size: TITLE_SIZE,
+                ..Default::default()
+            },
+            TextColor(TEXT_COLOR),
+        ));
+        for item in BindingsItem::all() {
+            parent.spawn((
+                Button,
+                item,
+                Text::new(item.text(&bindings, None, &strings)),
+                TextFont {
+                    font: font.clone(),
+                    font_size: TEXT_SIZE,
+                    ..Default::default()
+                },
+                TextColor(TEXT_COLOR),
+            ));
+        }
+    });
+}
+
+fn capture(
+    mut rebinding: ResMut<Rebinding>,
+    mut bindings: ResMut<InputBindings>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+) {
+    let Some(action) = **rebinding else { return };
+
+    if keyboard_input.just_pressed(CANCEL_KEYCODE) {
+        // debug!("capture: cancel");
+        **rebinding = None;
+        return
+    }
+    let binding = keyboard_input.get_just_pressed().next().map(|key| Binding::Key(*key))
+        .or_else(|| mouse_input.get_just_pressed().next().map(|button| Binding::Mouse(*button)))
+        .or_else(|| gamepads.iter().find_map(|gamepad| gamepad.get_just_pressed().next().map(|button| Binding::Gamepad(*button))));
+    let Some(binding) = binding else { return };
+    // debug!("capture: {:?} -> {:?}", action, binding);
+    bindings.bind(action, binding);
+    **rebinding = None;
+}
+
+fn navigate(
+    mut selected: ResMut<Selected>,
+    rebinding: Res<Rebinding>,
+    actions: Actions,
+    interaction_query: Query<(&Interaction, &BindingsItem), Changed<Interaction>>,
+) {
+    // the press that ended a rebinding is not a menu input
+    if rebinding.is_some() || rebinding.is_changed() { return }
+
+    let items = BindingsItem::all();
+    if actions.just_pressed(InputAction::MoveUp) { **selected = (**selected + items.len() - 1) % items.len() }
+    if actions.just_pressed(InputAction::MoveDown) { **selected = (**selected + 1) % items.len() }
+
+    for (interaction, item) in &interaction_query {
+        if *interaction == Interaction::None { continue }
+        let Some(index) = items.iter().position(|i| i == item) else { continue };
+        **selected = index;
+    }
+}
+
+fn select(
+    mut commands: Commands,
+    selected: Res<Selected>,
+    mut rebinding: ResMut<Rebinding>,
+    mut next_state: ResMut<NextState<OptionsState>>,
+    actions: Actions,
+    interaction_query: Query<&Interaction, (With<BindingsItem>, Changed<Interaction>)>,
+) {
+    if rebinding.is_some() || rebinding.is_changed() { return }
+
+    if actions.just_pressed(InputAction::Pause) {
+        next_state.set(OptionsState::General);
+        return
+    }
+    let clicked = interaction_query.iter().any(|interaction| *interaction == Interaction::Pressed);
+
+    if !clicked && !actions.just_pressed(InputAction::Confirm) { return }
+
+    let item = BindingsItem::all()[**selected];
+    // trace!("select: {:?}", item);
+    match item {
+        BindingsItem::Action(action) => **rebinding = Some(action),
+        // Actions already borrows the bindings
+        BindingsItem::Reset => commands.queue(|world: &mut World| world.resource_mut::<InputBindings>().reset()),
+        BindingsItem::Back => next_state.set(OptionsState::General),
+    }
+}
+
+fn refresh(
+    selected: Res<Selected>,
+    rebinding: Res<Rebinding>,
+    bindings: Res<InputBindings>,
+    strings: Res<Strings>,
+    mut query: Query<(&BindingsItem, &mut Text, &mut TextColor)>,
+) {
+    let items = BindingsItem::all();
+
+    for (item, mut text, mut color) in &mut query {
+        color.0 = if items[**selected] == *item { SELECTED_COLOR } else { TEXT_COLOR };
+
+        if !bindings.is_changed() && !rebinding.is_changed() && !strings.is_changed() { continue }
+        **text = item.text(&bindings, **rebinding, &strings);
+    }
+}
+
+fn all_despawn(
+    mut commands: Commands,
+    query: Query<Entity, With<BindingsMenu>>,
+) {
+    // debug!("all_despawn");
+    for entity in &query { commands.entity(entity).despawn_recursive() }
+}
+
+pub struct BindingsPlugin;
+
+impl Plugin for BindingsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Selected>()
+            .init_resource::<Rebinding>()
+            .add_systems(OnEnter(OptionsState::Bindings), setup)
+            .add_systems(Update, (
+                capture,
+                navigate,
+                select,
+                refresh,
+            ).chain().run_if(in_state(OptionsState::Bindings)))
+            .add_systems(OnExit(OptionsState::Bindings), all_despawn)
+        ;
+    }
+}
@@ -0,0 +1,242 @@
+use bevy::prelude::*;
+
+use crate::action::{
+    Actions,
+    InputAction,
+};
+use crate::locale::{
+    Localized,
+    Strings,
+};
+use crate::options::OptionsState;
+use crate::settings::{
+    DIFFICULTIES,
+    LANGUAGES,
+    WINDOW_SCALES,
+    Settings,
+    step_value,
+    step_volume,
+};
+
+const TITLE_TEXT: &str = "options.title";
+const ON_TEXT: &str = "options.on";
+const OFF_TEXT: &str = "options.off";
+const TITLE_SIZE: f32 = 24.0;
+const TEXT_SIZE: f32 = 14.0;
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const SELECTED_COLOR: Color = Color::srgb(0.9, 0.8, 0.3);
+const OVERLAY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.8);
+const ROW_GAP: f32 = 6.0;
+const ITEMS: [GeneralItem; 10] = [
+    GeneralItem::MasterVolume,
+    GeneralItem::SfxVolume,
+    GeneralItem::MusicVolume,
+    GeneralItem::WindowScale,
+    GeneralItem::Fullscreen,
+    GeneralItem::Language,
+    GeneralItem::ScreenShake,
+    GeneralItem::Difficulty,
+    GeneralItem::Bindings,
+    GeneralItem::Back,
+];
+
+#[derive(Component)]
+struct GeneralMenu;
+
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+enum GeneralItem {
+    MasterVolume,
+    SfxVolume,
+    MusicVolume,
+    WindowScale,
+    Fullscreen,
+    Language,
+    ScreenShake,
+    Difficulty,
+    Bindings,
+    Back,
+}
+
+#[derive(Resource, Deref, DerefMut, Default)]
+struct Selected(usize);
+
+impl GeneralItem {
+    fn text(&self, settings: &Settings, strings: &Strings) -> String {
+        let switch = |enabled: bool| strings.get(if enabled { ON_TEXT } else { OFF_TEXT });
+        let volume = |volume: f32| format!("{:>3}%", (volume * 100.0).round());
+        let label = strings.get(self.key());
+
+        match self {
+            GeneralItem::MasterVolume => format!("{}: {}", label, volume(settings.master_volume)),
+            GeneralItem::SfxVolume => format!("{}: {}", label, volume(settings.sfx_volume)),
+            GeneralItem::MusicVolume => format!("{}: {}", label, volume(settings.music_volume)),
+            GeneralItem::WindowScale => format!("{}: x{}", label, settings.window_scale),
+            GeneralItem::Fullscreen => format!("{}: {}", label, switch(settings.fullscreen)),
+            GeneralItem::Language => format!("{}: {}", label, settings.language.text()),
+            GeneralItem::ScreenShake => format!("{}: {}", label, switch(settings.screen_shake)),
+            GeneralItem::Difficulty => format!("{}: {}", label, strings.get(settings.difficulty.key())),
+            GeneralItem::Bindings | GeneralItem::Back => label.to_string(),
+        }
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            GeneralItem::MasterVolume => "options.master_volume",
+            GeneralItem::SfxVolume => "options.sfx_volume",
+            GeneralItem::MusicVolume => "options.music_volume",
+            GeneralItem::WindowScale => "options.window_scale",
+            GeneralItem::Fullscreen => "options.fullscreen",
+            GeneralItem::Language => "options.language",
+            GeneralItem::ScreenShake => "options.screen_shake",
+            GeneralItem::Difficulty => "options.difficulty",
+            GeneralItem::Bindings => "options.bindings",
+            GeneralItem::Back => "common.back",
+        }
+    }
+
+    fn adjust(&self, settings: &mut Settings, step: i32) {
+        match self {
+            GeneralItem::MasterVolume => settings.master_volume = step_volume(settings.master_volume, step),
+            GeneralItem::SfxVolume => settings.sfx_volume = step_volume(settings.sfx_volume, step),
+            GeneralItem::MusicVolume => settings.music_volume = step_volume(settings.music_volume, step),
+            GeneralItem::WindowScale => settings.window_scale = step_value(&WINDOW_SCALES, settings.window_scale, step),
+            GeneralItem::Fullscreen => settings.fullscreen = !settings.fullscreen,
+            GeneralItem::Language => settings.language = step_value(&LANGUAGES, settings.language, step),
+            GeneralItem::ScreenShake => settings.screen_shake = !settings.screen_shake,
+            GeneralItem::Difficulty => settings.difficulty = step_value(&DIFFICULTIES, settings.difficulty, step),
+            GeneralItem::Bindings | GeneralItem::Back => (),
+        }
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    mut selected: ResMut<Selected>,
+    settings: Res<Settings>,
+    strings: Res<Strings>,
+) {
+    // debug!("setup");
+    let font = strings.font();
+    **selected = 0;
+
+    commands.spawn((
+        GeneralMenu,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(ROW_GAP),
+            ..Default::default()
+        },
+        BackgroundColor(OVERLAY_COLOR),
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Localized(TITLE_TEXT),
+            Text::new(strings.get(TITLE_TEXT)),
+            TextFont {
+                font: font.clone(),
+                font_size: TITLE_SIZE,
+                ..Default::default()
+            },
+            TextColor(TEXT_COLOR),
+        ));
+        for item in ITEMS {
+            parent.spawn((
+                Button,
+                item,
+                Text::new(item.text(&settings, &strings)),
+                TextFont {
+                    font: font.clone(),
+                    font_size: TEXT_SIZE,
+                    ..Default::default()
+                },
+                TextColor(TEXT_COLOR),
+            ));
+        }
+    });
+}
+
+fn navigate(
+    mut selected: ResMut<Selected>,
+    actions: Actions,
+    interaction_query: Query<(&Interaction, &GeneralItem), Changed<Interaction>>,
+) {
+    if actions.just_pressed(InputAction::MoveUp) { **selected = (**selected + ITEMS.len() - 1) % ITEMS.len() }
+    if actions.just_pressed(InputAction::MoveDown) { **selected = (**selected + 1) % ITEMS.len() }
+
+    for (interaction, item) in &interaction_query {
+        if *interaction == Interaction::None { continue }
+        let Some(index) = ITEMS.iter().position(|i| i == item) else { continue };
+        **selected = index;
+    }
+}
+
+fn select(
+    selected: Res<Selected>,
+    mut settings: ResMut<Settings>,
+    mut next_state: ResMut<NextState<OptionsState>>,
+    actions: Actions,
+    interaction_query: Query<&Interaction, (With<GeneralItem>, Changed<Interaction>)>,
+) {
+    if actions.just_pressed(InputAction::Pause) {
+        next_state.set(OptionsState::Closed);
+        return
+    }
+    let item = ITEMS[**selected];
+    let clicked = interaction_query.iter().any(|interaction| *interaction == Interaction::Pressed);
+
+    if actions.just_pressed(InputAction::MoveLeft) { item.adjust(&mut settings, -1) }
+    if actions.just_pressed(InputAction::MoveRight) { item.adjust(&mut settings, 1) }
+
+    if !clicked && !actions.just_pressed(InputAction::Confirm) { return }
+
+    // trace!("select: {:?}", item);
+    match item {
+        GeneralItem::Bindings => next_state.set(OptionsState::Bindings),
+        GeneralItem::Back => next_state.set(OptionsState::Closed),
+        _ => item.adjust(&mut settings, 1),
+    }
+}
+
+fn refresh(
+    selected: Res<Selected>,
+    settings: Res<Settings>,
+    strings: Res<Strings>,
+    mut query: Query<(&GeneralItem, &mut Text, &mut TextColor)>,
+) {
+    for (item, mut text, mut color) in &mut query {
+        color.0 = if ITEMS[**selected] == *item { SELECTED_COLOR } else { TEXT_COLOR };
+
+        if !settings.is_changed() && !strings.is_changed() { continue }
+        **text = item.text(&settings, &strings);
+    }
+}
+
+fn all_despawn(
+    mut commands: Commands,
+    query: Query<Entity, With<GeneralMenu>>,
+) {
+    // debug!("all_despawn");
+    for entity in &query { commands.entity(entity).despawn_recursive() }
+}
+
+pub struct GeneralPlugin;
+
+impl Plugin for GeneralPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Selected>()
+            .add_systems(OnEnter(OptionsState::General), setup)
+            .add_systems(Update, (
+                navigate,
+                select,
+                refresh,
+            ).chain().run_if(in_state(OptionsState::General)))
+            .add_systems(OnExit(OptionsState::General), all_despawn)
+        ;
+    }
+}
@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+mod general;
+mod bindings;
+
+#[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OptionsState {
+    #[default]
+    Closed,
+    General,
+    Bindings,
+}
+
+pub struct OptionsPlugin;
+
+impl Plugin for OptionsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_state::<OptionsState>()
+            .add_plugins(general::GeneralPlugin)
+            .add_plugins(bindings::BindingsPlugin)
+        ;
+    }
+}
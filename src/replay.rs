@@ -0,0 +1,177 @@
+use bevy::prelude::*;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::{
+    AppState,
+    GameConfig,
+    storage,
+};
+use crate::ingame::TickSet;
+use crate::ingame::player::PlayerInput;
+use crate::ingame::rng::GameRng;
+use crate::settings::{
+    Difficulty,
+    Settings,
+};
+
+const FILE_NAME: &str = "replay.ron";
+const AXIS_SCALE: f32 = i8::MAX as f32;
+const SHOOT: u8 = 1 << 0;
+const BOMB: u8 = 1 << 1;
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+pub struct Replay {
+    pub seed: u64,
+    // replays recorded before difficulty settings were played on normal
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    // (x, y, button bits, number of ticks)
+    inputs: Vec<(i8, i8, u8, u32)>,
+}
+
+#[derive(Resource, Default)]
+pub struct ReplayState {
+    replay: Replay,
+    playback: bool,
+    cursor: usize,
+    repeat: u32,
+}
+
+impl Replay {
+    pub fn new(seed: u64, difficulty: Difficulty) -> Self {
+        Self { seed, difficulty, inputs: Vec::new(), }
+    }
+
+    pub fn load() -> Option<Self> {
+        let contents = storage::load(FILE_NAME)?;
+
+        match ron::from_str::<Self>(&contents) {
+            Ok(replay) => Some(replay),
+            Err(err) => {
+                warn!("failed to read replay: {}", err);
+                None
+            }
+        }
+    }
+
+    pub fn ticks(&self) -> usize {
+        self.inputs.iter().map(|(_, _, _, count)| *count as usize).sum()
+    }
+
+    // returns the input as it will be played back
+    fn push(&mut self, input: &PlayerInput) -> PlayerInput {
+        let (x, y, bits) = encode(input);
+
+        match self.inputs.last_mut() {
+            Some((last_x, last_y, last_bits, count)) if (*last_x, *last_y, *last_bits) == (x, y, bits) => *count += 1,
+            _ => self.inputs.push((x, y, bits, 1)),
+        }
+        decode(x, y, bits)
+    }
+
+    fn save(&self) {
+        let result = ron::to_string(self)
+            .map_err(|err| err.to_string())
+            .and_then(|contents| storage::save(FILE_NAME, &contents).map_err(|err| err.to_string()));
+
+        if let Err(err) = result { warn!("failed to save replay: {}", err) }
+    }
+}
+
+impl ReplayState {
+    pub fn playback(replay: Replay) -> Self {
+        Self { replay, playback: true, cursor: 0, repeat: 0, }
+    }
+
+    pub fn replay(&self) -> &Replay { &self.replay }
+
+    pub fn is_playback(&self) -> bool { self.playback }
+
+    pub fn is_finished(&self) -> bool { self.cursor >= self.replay.inputs.len() }
+
+    fn restart(&mut self, seed: u64, difficulty: Difficulty) {
+        if !self.playback { self.replay = Replay::new(seed, difficulty) }
+        self.cursor = 0;
+        self.repeat = 0;
+    }
+
+    fn next(&mut self) -> PlayerInput {
+        let Some((x, y, bits, count)) = self.replay.inputs.get(self.cursor) else { return PlayerInput::default() };
+        let input = decode(*x, *y, *bits);
+
+        self.repeat += 1;
+        if self.repeat >= *count {
+            self.cursor += 1;
+            self.repeat = 0;
+        }
+        input
+    }
+}
+
+fn encode(input: &PlayerInput) -> (i8, i8, u8) {
+    let axis = |value: f32| (value.clamp(-1.0, 1.0) * AXIS_SCALE).round() as i8;
+    let mut bits = 0;
+
+    if input.shoot { bits |= SHOOT }
+    if input.bomb { bits |= BOMB }
+    (axis(input.direction.x), axis(input.direction.y), bits)
+}
+
+fn decode(x: i8, y: i8, bits: u8) -> PlayerInput {
+    PlayerInput {
+        direction: Vec2::new(x as f32, y as f32) / AXIS_SCALE,
+        shoot: bits & SHOOT != 0,
+        bomb: bits & BOMB != 0,
+    }
+}
+
+fn start(
+    mut commands: Commands,
+    mut state: ResMut<ReplayState>,
+    config: Res<GameConfig>,
+    settings: Res<Settings>,
+) {
+    let (seed, difficulty) = if state.playback {
+        (state.replay.seed, state.replay.difficulty)
+    } else {
+        (config.seed.unwrap_or_else(rand::random), settings.difficulty)
+    };
+    // debug!("start: {} {:?}", seed, difficulty);
+    state.restart(seed, difficulty);
+    commands.insert_resource(GameRng::new(seed));
+    commands.insert_resource(difficulty);
+}
+
+fn tick(
+    mut state: ResMut<ReplayState>,
+    mut input: ResMut<PlayerInput>,
+) {
+    // the recorded run uses the quantized input too, so playback matches it exactly
+    *input = if state.playback { state.next() } else { state.replay.push(&input) };
+}
+
+fn save(
+    state: Res<ReplayState>,
+    config: Res<GameConfig>,
+) {
+    if state.playback || !config.persistent { return }
+    // debug!("save");
+    state.replay.save();
+}
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ReplayState>()
+            .add_systems(OnEnter(AppState::Mainmenu), start)
+            .add_systems(OnEnter(AppState::Restart), start)
+            .add_systems(FixedUpdate, tick.in_set(TickSet::Input))
+            .add_systems(OnEnter(AppState::Gameover), save)
+        ;
+    }
+}
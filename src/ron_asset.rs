@@ -0,0 +1,53 @@
+use bevy::{
+    prelude::*,
+    asset::{io::Reader, AssetLoader, LoadContext},
+};
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+use thiserror::Error;
+
+pub trait RonAsset: Asset + DeserializeOwned {
+    const EXTENSIONS: &'static [&'static str];
+
+    // values that parse but cannot be used, reported as a load error
+    fn validate(&self) -> Result<(), String> { Ok(()) }
+}
+
+pub struct RonAssetLoader<A>(PhantomData<A>);
+
+impl<A> Default for RonAssetLoader<A> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+#[derive(Debug, Error)]
+pub enum RonAssetLoaderError {
+    #[error("could not read asset: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse asset: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("invalid asset: {0}")]
+    Invalid(String),
+}
+
+impl<A: RonAsset> AssetLoader for RonAssetLoader<A> {
+    type Asset = A;
+    type Settings = ();
+    type Error = RonAssetLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let asset = ron::de::from_bytes::<A>(&bytes)?;
+        asset.validate().map_err(RonAssetLoaderError::Invalid)?;
+        Ok(asset)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        A::EXTENSIONS
+    }
+}
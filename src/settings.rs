@@ -0,0 +1,178 @@
+use bevy::{
+    prelude::*,
+    window::{
+        MonitorSelection,
+        PrimaryWindow,
+        WindowMode,
+        WindowResolution,
+    },
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::{
+    GameConfig,
+    storage,
+};
+
+const FILE_NAME: &str = "settings.ron";
+const VOLUME_LEVELS: i32 = 10;
+pub const WINDOW_SCALES: [f32; 3] = [1.0, 1.5, 2.0];
+pub const LANGUAGES: [Language; 2] = [
+    Language::Japanese,
+    Language::English,
+];
+pub const DIFFICULTIES: [Difficulty; 3] = [
+    Difficulty::Easy,
+    Difficulty::Normal,
+    Difficulty::Hard,
+];
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Language {
+    #[default]
+    Japanese,
+    English,
+}
+
+#[derive(Resource, Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    pub master_volume: f32,
+    pub sfx_volume: f32,
+    pub music_volume: f32,
+    pub window_scale: f32,
+    pub fullscreen: bool,
+    pub language: Language,
+    pub screen_shake: bool,
+    pub difficulty: Difficulty,
+}
+
+impl Language {
+    // each language is listed under its own name
+    pub fn text(&self) -> &'static str {
+        match self {
+            Language::Japanese => "日本語",
+            Language::English => "English",
+        }
+    }
+}
+
+impl Difficulty {
+    pub fn key(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "difficulty.easy",
+            Difficulty::Normal => "difficulty.normal",
+            Difficulty::Hard => "difficulty.hard",
+        }
+    }
+
+    // multiplier for how fast enemy emitters fire
+    pub fn fire_rate(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.7,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.4,
+        }
+    }
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        let Some(contents) = storage::load_config(FILE_NAME) else { return Self::default() };
+
+        match ron::from_str::<Self>(&contents) {
+            Ok(settings) => settings,
+            Err(err) => {
+                warn!("failed to read settings: {}", err);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn window_resolution(&self, window_size: Vec2) -> WindowResolution {
+        let size = window_size * self.window_scale;
+
+        WindowResolution::new(size.x, size.y).with_scale_factor_override(self.window_scale)
+    }
+
+    pub fn window_mode(&self) -> WindowMode {
+        if self.fullscreen { WindowMode::BorderlessFullscreen(MonitorSelection::Current) } else { WindowMode::Windowed }
+    }
+
+    fn save(&self) {
+        let result = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())
+            .and_then(|contents| storage::save_config(FILE_NAME, &contents).map_err(|err| err.to_string()));
+
+        if let Err(err) = result { warn!("failed to save settings: {}", err) }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.0,
+            sfx_volume: 1.0,
+            music_volume: 1.0,
+            window_scale: WINDOW_SCALES[0],
+            fullscreen: false,
+            language: Language::default(),
+            screen_shake: true,
+            difficulty: Difficulty::default(),
+        }
+    }
+}
+
+// volumes move in steps of 10% and stop at 0% and 100%
+pub fn step_volume(volume: f32, step: i32) -> f32 {
+    let level = (volume * VOLUME_LEVELS as f32).round() as i32;
+
+    (level + step).clamp(0, VOLUME_LEVELS) as f32 / VOLUME_LEVELS as f32
+}
+
+pub fn step_value<T: Copy + PartialEq>(values: &[T], current: T, step: i32) -> T {
+    let index = values.iter().position(|value| *value == current).unwrap_or(0) as i32;
+
+    values[(index + step).rem_euclid(values.len() as i32) as usize]
+}
+
+fn apply(
+    mut commands: Commands,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    settings: Res<Settings>,
+    config: Res<GameConfig>,
+) {
+    // debug!("apply: {:?}", settings);
+    commands.insert_resource(GlobalVolume::new(settings.master_volume));
+
+    if let Ok(mut window) = window_query.get_single_mut() {
+        window.resolution.set_scale_factor_override(Some(settings.window_scale));
+        window.resolution.set(config.window_size.x, config.window_size.y);
+        window.mode = settings.window_mode();
+    }
+    // the settings inserted at startup came from the file already
+    if settings.is_added() || !config.persistent { return }
+    settings.save();
+}
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Difficulty>()
+            .add_systems(Update, apply.run_if(resource_changed::<Settings>))
+        ;
+    }
+}
@@ -0,0 +1,75 @@
+use std::io;
+
+const APP_DIR: &str = "ittoku-2dshooting-game";
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load(key: &str) -> Option<String> {
+    read(dirs::data_dir()?, key)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save(key: &str, value: &str) -> io::Result<()> {
+    let dir = dirs::data_dir().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+
+    write(dir, key, value)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_config(key: &str) -> Option<String> {
+    read(dirs::config_dir()?, key)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_config(key: &str, value: &str) -> io::Result<()> {
+    let dir = dirs::config_dir().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+
+    write(dir, key, value)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read(dir: std::path::PathBuf, key: &str) -> Option<String> {
+    std::fs::read_to_string(dir.join(APP_DIR).join(key)).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write(dir: std::path::PathBuf, key: &str, value: &str) -> io::Result<()> {
+    let dir = dir.join(APP_DIR);
+
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join(key), value)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn load(key: &str) -> Option<String> {
+    local_storage()?.get_item(&storage_key(key)).ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save(key: &str, value: &str) -> io::Result<()> {
+    let storage = local_storage()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no localStorage"))?;
+
+    storage.set_item(&storage_key(key), value)
+        .map_err(|_| io::Error::other("failed to write localStorage"))
+}
+
+// the browser has a single storage for data and config
+#[cfg(target_arch = "wasm32")]
+pub fn load_config(key: &str) -> Option<String> {
+    load(key)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save_config(key: &str, value: &str) -> io::Result<()> {
+    save(key, value)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn storage_key(key: &str) -> String {
+    format!("{}/{}", APP_DIR, key)
+}
@@ -0,0 +1,799 @@
+use bevy::{
+    prelude::*,
+    math::bounding::Aabb2d,
+};
+use rand::RngCore;
+use ittoku_2dshooting_game::{
+    AppState,
+    MyCamera,
+    action::{
+        Binding,
+        InputAction,
+        InputBindings,
+    },
+    GamePlugin,
+    Kills,
+    Score,
+    headless::HeadlessApp,
+    highscore::{
+        HighScore,
+        HighScores,
+    },
+    ingame::{
+        BossBarFill,
+        CollisionLayers,
+        Hitbox,
+        Shape,
+        SpatialGrid,
+    },
+    ingame::boss::Boss,
+    ingame::enemy::{
+        BulletEmitter,
+        BulletPattern,
+        BulletType,
+        EmitterDefinition,
+        Enemy,
+        EnemyDefinition,
+        EnemyDefinitions,
+        Health,
+    },
+    ingame::item::{
+        Item,
+        ItemKind,
+    },
+    ingame::player::{
+        Invulnerable,
+        Player,
+    },
+    ingame::rng::GameRng,
+    ingame::stage::{
+        EntrySide,
+        Formation,
+        StageDefinition,
+        StageDefinitions,
+        Wave,
+        WaveTrigger,
+    },
+    loading::{
+        GameAssets,
+        LoadingProgress,
+    },
+    locale::{
+        Localized,
+        Strings,
+    },
+    music::{
+        Bgm,
+        Track,
+    },
+    settings::{
+        Difficulty,
+        Language,
+        Settings,
+        step_volume,
+    },
+};
+
+fn tap(app: &mut HeadlessApp, key: KeyCode) {
+    app.press(key);
+    app.step(2);
+    app.release(key);
+    app.step(1);
+}
+
+#[test]
+fn ingame_runs_without_window() {
+    let mut app = HeadlessApp::new();
+    let report = app.step(60);
+
+    assert_eq!(report.ticks, 60);
+    assert_eq!(report.state, AppState::Ingame);
+    assert_eq!(report.stage, 0);
+    assert_eq!(report.enemies, 4);
+    assert!(report.player.is_some());
+}
+
+#[test]
+fn player_moves_and_shoots() {
+    let mut app = HeadlessApp::new();
+    let start = app.step(1).player.unwrap();
+
+    app.press(KeyCode::KeyD);
+    app.press(KeyCode::Space);
+    let player = app.step(10).player.unwrap();
+
+    assert!(player.position.x > start.position.x);
+    assert_eq!(player.bullets, start.bullets - 1);
+}
+
+#[test]
+fn bullets_leave_the_configured_window() {
+    let shoot = |game: GamePlugin| {
+        let mut app = HeadlessApp::with_plugin(game.seed(42).persistent(false).background(false).mainmenu(false));
+        app.wait_for_assets();
+        app.app_mut().world_mut().resource_mut::<NextState<AppState>>().set(AppState::Ingame);
+        let start = app.step(1).player.unwrap();
+        app.press(KeyCode::Space);
+        app.step(1);
+        app.release(KeyCode::Space);
+        (0..).find(|_| app.step(1).player.unwrap().bullets == start.bullets).unwrap()
+    };
+
+    // the shot is refunded once it passes the top of the window the game was given
+    let default = shoot(GamePlugin::new());
+    let tall = shoot(GamePlugin::new().window_size(Vec2::new(640.0, 960.0)));
+    assert!(tall > default + 20, "{} vs {}", tall, default);
+}
+
+#[test]
+fn bomb_destroys_enemies_on_screen() {
+    let mut app = HeadlessApp::new();
+    let start = app.step(60);
+
+    app.press(KeyCode::KeyX);
+    let report = app.step(2);
+
+    assert_eq!(report.player.unwrap().bombs, start.player.unwrap().bombs - 1);
+    assert_eq!(report.fighter_kills, start.enemies);
+    assert_eq!(report.enemies, 0);
+}
+
+#[test]
+fn pause_freezes_simulation() {
+    let mut app = HeadlessApp::new();
+    app.step(30);
+
+    app.press(KeyCode::Escape);
+    let paused = app.step(2);
+    app.release(KeyCode::Escape);
+    assert!(paused.paused);
+
+    app.press(KeyCode::KeyD);
+    let report = app.step(30);
+    assert_eq!(report.player, paused.player);
+    assert_eq!(report.enemies, paused.enemies);
+
+    app.release(KeyCode::KeyD);
+    app.press(KeyCode::Escape);
+    let resumed = app.step(2);
+    assert!(!resumed.paused);
+}
+
+#[test]
+fn highscore_is_recorded_after_name_entry() {
+    let mut app = HeadlessApp::new();
+    app.step(1);
+
+    let world = app.app_mut().world_mut();
+    world.resource_mut::<Score>().add("fighter", 10);
+    for mut player in world.query::<&mut Player>().iter_mut(world) { player.hp = 0 }
+    assert_eq!(app.step(3).state, AppState::Gameover);
+
+    for key in [KeyCode::KeyA, KeyCode::KeyB, KeyCode::Enter] {
+        app.press(key);
+        app.step(1);
+        app.release(key);
+        app.step(1);
+    }
+
+    let report = app.report();
+    let highscores = app.app().world().resource::<HighScores>();
+    assert_eq!(report.state, AppState::Gameover);
+    assert_eq!(highscores.entries().len(), 1);
+    assert_eq!(highscores.entries()[0].name, "AB");
+    assert_eq!(highscores.entries()[0].score, 10);
+}
+
+#[test]
+fn gamepad_name_entry_uses_the_letter_moved_to() {
+    let mut app = HeadlessApp::new();
+    let gamepad = app.connect_gamepad();
+    app.step(1);
+
+    let world = app.app_mut().world_mut();
+    world.resource_mut::<Score>().add("fighter", 10);
+    for mut player in world.query::<&mut Player>().iter_mut(world) { player.hp = 0 }
+    assert_eq!(app.step(3).state, AppState::Gameover);
+
+    // moving down and confirming on the same frame picks the new letter
+    for buttons in [vec![GamepadButton::DPadDown, GamepadButton::South], vec![GamepadButton::Start]] {
+        for button in &buttons { app.press_button(gamepad, *button) }
+        app.step(1);
+        for button in &buttons { app.release_button(gamepad, *button) }
+        app.step(1);
+    }
+
+    let highscores = app.app().world().resource::<HighScores>();
+    assert_eq!(highscores.entries()[0].name, "B");
+}
+
+#[test]
+fn replay_reproduces_run() {
+    let mut app = HeadlessApp::new();
+    app.step(1);
+
+    for (keys, ticks) in [
+        (vec![KeyCode::KeyD, KeyCode::Space], 20),
+        (vec![KeyCode::KeyW, KeyCode::KeyA], 40),
+        (vec![KeyCode::Space], 30),
+        (vec![KeyCode::KeyX], 60),
+    ] {
+        for key in &keys { app.press(*key) }
+        app.step(ticks);
+        for key in &keys { app.release(*key) }
+    }
+    let recorded = app.step(120);
+    let replay = app.replay().clone();
+    assert_eq!(replay.seed, app.app().world().resource::<GameRng>().seed());
+    assert!(replay.ticks() > 0);
+    assert!(recorded.fighter_kills > 0);
+
+    let mut playback = HeadlessApp::playback(replay.clone());
+    let report = playback.step(recorded.ticks);
+
+    assert_eq!(playback.app().world().resource::<GameRng>().seed(), replay.seed);
+    assert_eq!(report, recorded);
+}
+
+#[test]
+fn seed_reproduces_enemy_layout() {
+    let layout = |seed: u64| {
+        let mut app = HeadlessApp::with_seed(seed);
+        app.step(60);
+        let world = app.app_mut().world_mut();
+        assert_eq!(world.resource::<GameRng>().seed(), seed);
+        world.query_filtered::<&Transform, With<Enemy>>()
+            .iter(world)
+            .map(|transform| transform.translation.xy())
+            .collect::<Vec<_>>()
+    };
+
+    let first = layout(42);
+    assert!(!first.is_empty());
+    assert_eq!(first, layout(42));
+    assert_ne!(first, layout(43));
+}
+
+#[test]
+fn simulation_speed_is_independent_of_frame_rate() {
+    let travel = |fps: u32| {
+        let mut app = HeadlessApp::with_seed(7);
+        app.set_frame_rate(fps);
+        let start = app.step(1).player.unwrap().position;
+
+        app.press(KeyCode::KeyD);
+        let player = app.step(fps).player.unwrap();
+        let world = app.app_mut().world_mut();
+        let camera = world.query_filtered::<&Transform, With<MyCamera>>().single(world);
+        (player.position.x - start.x, camera.translation.y)
+    };
+
+    let (player_60, camera_60) = travel(60);
+    let (player_144, camera_144) = travel(144);
+    assert!(player_60 > 0.0);
+    assert!((player_60 - player_144).abs() < 8.0, "{} vs {}", player_60, player_144);
+    assert!((camera_60 - camera_144).abs() < 1.0, "{} vs {}", camera_60, camera_144);
+}
+
+#[test]
+fn gamepad_moves_with_analog_stick() {
+    let mut keyboard = HeadlessApp::new();
+    let keyboard_start = keyboard.step(1).player.unwrap();
+    keyboard.press(KeyCode::KeyD);
+    let full = keyboard.step(30).player.unwrap().position.x - keyboard_start.position.x;
+
+    let mut app = HeadlessApp::new();
+    let gamepad = app.connect_gamepad();
+    let start = app.step(1).player.unwrap();
+
+    app.move_stick(gamepad, Vec2::new(0.1, 0.0));
+    let idle = app.step(10).player.unwrap();
+    assert_eq!(idle.position.x, start.position.x);
+
+    app.move_stick(gamepad, Vec2::new(0.6, 0.0));
+    app.press_button(gamepad, GamepadButton::South);
+    let player = app.step(30).player.unwrap();
+    let ratio = (player.position.x - idle.position.x) / full;
+    assert!(ratio > 0.3 && ratio < 0.7, "ratio: {}", ratio);
+    assert_eq!(player.bullets, start.bullets - 1);
+}
+
+#[test]
+fn shoot_can_be_rebound_from_the_options_menu() {
+    let mut app = HeadlessApp::new();
+    let start = app.step(1).player.unwrap();
+
+    // pause, open the key bindings from the options and rebind shoot to J
+    tap(&mut app, KeyCode::Escape);
+    for key in [KeyCode::KeyS, KeyCode::KeyS, KeyCode::Enter] { tap(&mut app, key) }
+    for key in [KeyCode::KeyW, KeyCode::KeyW, KeyCode::Enter] { tap(&mut app, key) }
+    for _ in 0..4 { tap(&mut app, KeyCode::KeyS) }
+    tap(&mut app, KeyCode::Enter);
+    tap(&mut app, KeyCode::KeyJ);
+    let bindings = app.app().world().resource::<InputBindings>();
+    assert_eq!(bindings.get(InputAction::Shoot), [
+        Binding::Gamepad(GamepadButton::South),
+        Binding::Key(KeyCode::KeyJ),
+    ]);
+
+    // back to the options, the pause menu and resume
+    for _ in 0..3 { tap(&mut app, KeyCode::Escape) }
+    assert!(!app.report().paused);
+
+    tap(&mut app, KeyCode::Space);
+    assert_eq!(app.report().player.unwrap().bullets, start.bullets);
+    tap(&mut app, KeyCode::KeyJ);
+    assert_eq!(app.report().player.unwrap().bullets, start.bullets - 1);
+}
+
+#[test]
+fn difficulty_from_the_options_menu_applies_on_restart() {
+    let mut app = HeadlessApp::new();
+    app.step(1);
+
+    // pause, open the options and raise the difficulty
+    tap(&mut app, KeyCode::Escape);
+    for key in [KeyCode::KeyS, KeyCode::KeyS, KeyCode::Enter] { tap(&mut app, key) }
+    for _ in 0..7 { tap(&mut app, KeyCode::KeyS) }
+    tap(&mut app, KeyCode::KeyD);
+    let world = app.app().world();
+    assert_eq!(world.resource::<Settings>().difficulty, Difficulty::Hard);
+    assert_eq!(*world.resource::<Difficulty>(), Difficulty::Normal);
+
+    // the current run keeps its difficulty until it restarts
+    tap(&mut app, KeyCode::Escape);
+    for key in [KeyCode::KeyS, KeyCode::Enter] { tap(&mut app, key) }
+    app.step(2);
+    assert_eq!(*app.app().world().resource::<Difficulty>(), Difficulty::Hard);
+    assert_eq!(app.replay().difficulty, Difficulty::Hard);
+    assert!(!app.report().paused);
+}
+
+#[test]
+fn volume_stops_at_the_ends() {
+    assert_eq!(step_volume(1.0, 1), 1.0);
+    assert_eq!(step_volume(0.0, -1), 0.0);
+    assert_eq!(step_volume(0.9, 1), 1.0);
+    assert_eq!(step_volume(0.5, -1), 0.4);
+}
+
+#[test]
+fn language_switch_rebuilds_visible_text() {
+    let mut app = HeadlessApp::new();
+    app.step(1);
+    let texts = |app: &mut HeadlessApp| {
+        let world = app.app_mut().world_mut();
+        world.query_filtered::<&Text, With<Localized>>().iter(world).map(|text| text.0.clone()).collect::<Vec<_>>()
+    };
+    assert!(texts(&mut app).contains(&"スコア: ".to_string()));
+
+    // pause, open the options and switch to english
+    tap(&mut app, KeyCode::Escape);
+    for key in [KeyCode::KeyS, KeyCode::KeyS, KeyCode::Enter] { tap(&mut app, key) }
+    for _ in 0..5 { tap(&mut app, KeyCode::KeyS) }
+    tap(&mut app, KeyCode::KeyD);
+    assert_eq!(app.app().world().resource::<Settings>().language, Language::English);
+
+    let texts = texts(&mut app);
+    assert!(texts.contains(&"Score: ".to_string()));
+    assert!(texts.contains(&"Options".to_string()));
+    // english falls back to the font built into bevy
+    let world = app.app_mut().world_mut();
+    assert!(world.query::<&TextFont>().iter(world).all(|font| font.font == Handle::default()));
+}
+
+#[test]
+fn font_path_overrides_the_string_table_font() {
+    let game = GamePlugin::new()
+        .persistent(false)
+        .background(false)
+        .mainmenu(false)
+        .font_path("fonts/misaki_gothic.ttf");
+    let mut app = HeadlessApp::with_plugin(game);
+    app.wait_for_assets();
+    app.app_mut().world_mut().resource_mut::<NextState<AppState>>().set(AppState::Ingame);
+    app.step(1);
+
+    // english has no font of its own, the override still wins
+    app.app_mut().world_mut().resource_mut::<Settings>().language = Language::English;
+    app.step(1);
+    let world = app.app_mut().world_mut();
+    let font = world.resource::<AssetServer>().load::<Font>("fonts/misaki_gothic.ttf");
+    assert_eq!(world.resource::<Strings>().font(), font);
+    assert!(world.query::<&TextFont>().iter(world).all(|text_font| text_font.font == font));
+}
+
+#[test]
+fn music_crossfades_between_tracks() {
+    let mut app = HeadlessApp::new();
+    app.step(1);
+    let tracks = |app: &mut HeadlessApp| {
+        let world = app.app_mut().world_mut();
+        world.query::<&Bgm>().iter(world).map(|bgm| (bgm.track(), bgm.is_fading_out())).collect::<Vec<_>>()
+    };
+
+    // the title music has faded out once the crossfade is over
+    assert!(tracks(&mut app).contains(&(Track::Stage, false)));
+    app.step(120);
+    assert_eq!(tracks(&mut app), [(Track::Stage, false)]);
+
+    // a boss on screen overrides the stage music until it is gone
+    let world = app.app_mut().world_mut();
+    let definitions = world.resource::<EnemyDefinitions>();
+    let (_, definition) = definitions.get("boss", world.resource::<Assets<EnemyDefinition>>()).unwrap();
+    let boss = Boss::new(definition);
+    let boss = world.spawn(boss).id();
+    app.step(30);
+    let playing = tracks(&mut app);
+    assert!(playing.contains(&(Track::Stage, true)));
+    assert!(playing.contains(&(Track::Boss, false)));
+
+    app.app_mut().world_mut().despawn(boss);
+    app.step(1);
+    let playing = tracks(&mut app);
+    assert!(playing.contains(&(Track::Stage, false)));
+    assert!(playing.contains(&(Track::Boss, true)));
+
+    let world = app.app_mut().world_mut();
+    for mut player in world.query::<&mut Player>().iter_mut(world) { player.hp = 0 }
+    assert_eq!(app.step(3).state, AppState::Gameover);
+    assert!(tracks(&mut app).contains(&(Track::Gameover, false)));
+}
+
+#[test]
+fn spatial_grid_reports_each_overlap_once() {
+    let mut grid = SpatialGrid::default();
+    let (wide, small, far) = (Entity::from_raw(1), Entity::from_raw(2), Entity::from_raw(3));
+
+    // the wide box spans several cells and the small one sits on a cell corner
+    grid.insert(wide, Aabb2d::new(Vec2::ZERO, Vec2::new(40.0, 8.0)));
+    grid.insert(small, Aabb2d::new(Vec2::splat(16.0), Vec2::splat(2.0)));
+    grid.insert(far, Aabb2d::new(Vec2::splat(200.0), Vec2::splat(4.0)));
+
+    let found = grid.query(Aabb2d::new(Vec2::new(8.0, 8.0), Vec2::splat(10.0))).collect::<Vec<_>>();
+    assert_eq!(found, [wide, small]);
+    let found = grid.query(Aabb2d::new(Vec2::ZERO, Vec2::splat(64.0))).collect::<Vec<_>>();
+    assert_eq!(found.len(), 2);
+    assert!(!found.contains(&far));
+
+    grid.clear();
+    assert_eq!(grid.query(Aabb2d::new(Vec2::ZERO, Vec2::splat(256.0))).count(), 0);
+}
+
+#[test]
+fn hazard_layer_damages_player_without_a_new_check() {
+    let mut app = HeadlessApp::new();
+    let start = app.step(1).player.unwrap();
+
+    // a bare collider on the hazard layer is enough for the player to take damage
+    let hazard = app.app_mut().world_mut().spawn((
+        CollisionLayers::HAZARD,
+        Hitbox::rect(Vec2::splat(64.0)),
+        Transform::from_translation(start.position.extend(0.0)),
+    )).id();
+    let player = app.step(2).player.unwrap();
+    assert_eq!(player.hp, start.hp - 1);
+
+    // the hazard stays, but the invulnerability absorbs it for a while
+    let player = app.step(30).player.unwrap();
+    assert_eq!(player.hp, start.hp - 1);
+    assert!(app.app().world().get_entity(hazard).is_ok());
+}
+
+#[test]
+fn enemy_without_explosion_frames_is_rejected() {
+    let contents = std::fs::read_to_string("assets/enemies/fighter.enemy.ron").unwrap();
+    let definition = ron::from_str::<EnemyDefinition>(&contents).unwrap();
+    assert!(definition.validate().is_ok());
+
+    let definition = EnemyDefinition { explosion_frames: 0, ..definition };
+    assert!(definition.validate().unwrap_err().contains("explosion_frames"));
+}
+
+#[test]
+fn hits_knock_back_and_leave_the_player_invulnerable() {
+    let mut app = HeadlessApp::with_seed(42);
+    let start = app.step(1).player.unwrap();
+
+    // a hazard just below the player pushes it up and away
+    app.app_mut().world_mut().spawn((
+        CollisionLayers::HAZARD,
+        Hitbox::rect(Vec2::splat(64.0)),
+        Transform::from_translation((start.position - Vec2::new(0.0, 8.0)).extend(0.0)),
+    ));
+    let hit = app.step(2).player.unwrap();
+    assert_eq!(hit.hp, start.hp - 1);
+    assert!(hit.position.y > start.position.y);
+
+    // nothing more is taken until the window closes, even when sitting on the hazard
+    let world = app.app_mut().world_mut();
+    assert_eq!(world.query_filtered::<(), With<Invulnerable>>().iter(world).count(), 1);
+    world.spawn((
+        CollisionLayers::HAZARD,
+        Hitbox::rect(Vec2::splat(64.0)),
+        Transform::from_translation(hit.position.extend(0.0)),
+    ));
+    assert_eq!(app.step(60).player.unwrap().hp, start.hp - 1);
+    // the window is 1.5s, then the same hazard hits again
+    assert_eq!(app.step(40).player.unwrap().hp, start.hp - 2);
+}
+
+#[test]
+fn a_downed_player_is_not_hit_again() {
+    let game = GamePlugin::new().seed(42).invulnerable_duration(0.0);
+    let mut app = HeadlessApp::with_plugin(game.persistent(false).background(false).mainmenu(false));
+    app.wait_for_assets();
+    app.app_mut().world_mut().resource_mut::<NextState<AppState>>().set(AppState::Ingame);
+    let start = app.step(1).player.unwrap();
+
+    // without a window every tick is a hit, the last one must not go below zero
+    let world = app.app_mut().world_mut();
+    for mut player in world.query::<&mut Player>().iter_mut(world) { player.hp = 1 }
+    world.spawn((
+        CollisionLayers::HAZARD,
+        Hitbox::rect(Vec2::splat(64.0)),
+        Transform::from_translation(start.position.extend(0.0)),
+    ));
+    assert_eq!(app.step(5).state, AppState::Gameover);
+}
+
+#[test]
+fn killed_enemies_drop_items_on_the_same_tick() {
+    let mut app = HeadlessApp::with_seed(42);
+    let start = app.step(1).player.unwrap();
+
+    // the boss always drops, killed just above the player
+    let world = app.app_mut().world_mut();
+    let definitions = world.resource::<EnemyDefinitions>();
+    let (handle, definition) = definitions.get("boss", world.resource::<Assets<EnemyDefinition>>()).unwrap();
+    let translation = (start.position + Vec2::new(0.0, 40.0)).extend(0.0);
+    let enemy = Enemy::new(handle, definition, Handle::default(), translation, Vec2::ZERO, 60.0);
+    world.spawn(enemy).insert(Health(0));
+    app.step(1);
+
+    let world = app.app_mut().world_mut();
+    let items = world.query::<&Item>().iter(world).map(|item| item.0).collect::<Vec<_>>();
+    assert_eq!(items, [ItemKind::Power]);
+
+    // it falls onto the player and powers up the weapon
+    let report = app.step(60);
+    let world = app.app_mut().world_mut();
+    assert_eq!(world.query::<&Item>().iter(world).count(), 0);
+    assert_eq!(report.player.unwrap().weapon, start.weapon + 1);
+}
+
+#[test]
+fn boss_phases_follow_its_health_bar() {
+    fn boss(app: &mut HeadlessApp, hp: usize) -> (usize, Option<Val>) {
+        let world = app.app_mut().world_mut();
+        for mut health in world.query_filtered::<&mut Health, With<Boss>>().iter_mut(world) { **health = hp }
+        app.step(2);
+        let world = app.app_mut().world_mut();
+        let phase = world.query::<&Boss>().single(world).phase;
+        let fill = world.query_filtered::<&Node, With<BossBarFill>>().get_single(world).ok().map(|node| node.width);
+        (phase, fill)
+    }
+
+    let mut app = HeadlessApp::with_seed(42);
+    app.step(1);
+    let world = app.app_mut().world_mut();
+    let definitions = world.resource::<EnemyDefinitions>();
+    let (handle, definition) = definitions.get("boss", world.resource::<Assets<EnemyDefinition>>()).unwrap();
+    let max_hp = definition.hp;
+    let enemy = Enemy::new(handle, definition, Handle::default(), Vec3::new(0.0, 200.0, 10.0), Vec2::ZERO, 60.0);
+    let phases = Boss::new(definition);
+    world.spawn((enemy, phases));
+
+    // the thresholds are 0.66 and 0.33 of the health
+    let percent = |hp: usize| Some(Val::Percent(hp as f32 / max_hp as f32 * 100.0));
+    assert_eq!(boss(&mut app, max_hp), (0, percent(max_hp)));
+    assert_eq!(boss(&mut app, 40), (0, percent(40)));
+    assert_eq!(boss(&mut app, 39), (1, percent(39)));
+    assert_eq!(boss(&mut app, 19), (2, percent(19)));
+
+    // leaving with the bar still up takes it down once
+    app.app_mut().world_mut().resource_mut::<NextState<AppState>>().set(AppState::Mainmenu);
+    app.step(2);
+    let world = app.app_mut().world_mut();
+    assert_eq!(world.query_filtered::<(), With<BossBarFill>>().iter(world).count(), 0);
+    assert_eq!(world.query::<&Boss>().iter(world).count(), 0);
+}
+
+#[test]
+fn a_short_stage_runs_through_to_the_next() {
+    fn kill_all(app: &mut HeadlessApp) {
+        let world = app.app_mut().world_mut();
+        for mut health in world.query_filtered::<&mut Health, With<Enemy>>().iter_mut(world) { **health = 0 }
+        app.step(2);
+    }
+    fn bosses(app: &mut HeadlessApp) -> usize {
+        let world = app.app_mut().world_mut();
+        world.query::<&Boss>().iter(world).count()
+    }
+    fn wave(trigger: WaveTrigger, enemy: &str, count: usize) -> Wave {
+        Wave { trigger, enemy: enemy.to_string(), count, formation: Formation::Line, side: EntrySide::Top, offset: 0.0 }
+    }
+
+    // the first stage cut down to a wave on a timer, one on the scroll distance and the boss
+    let mut app = HeadlessApp::with_seed(42);
+    let world = app.app_mut().world_mut();
+    let stage = world.resource::<StageDefinitions>().get(0).unwrap();
+    world.resource_mut::<Assets<StageDefinition>>().insert(&stage, StageDefinition {
+        name: "stage.1".to_string(),
+        waves: vec![
+            wave(WaveTrigger::Time(0.5), "fighter", 2),
+            wave(WaveTrigger::Distance(24.0), "torpedo", 1),
+        ],
+        boss: Some("boss".to_string()),
+    });
+
+    assert_eq!(app.step(20).enemies, 0);
+    assert_eq!(app.step(20).enemies, 2);
+    kill_all(&mut app);
+    assert_eq!(app.report().enemies, 0);
+    assert_eq!(bosses(&mut app), 0);
+
+    // the camera scrolls 12px a second
+    assert_eq!(app.step(120).enemies, 1);
+    assert_eq!(bosses(&mut app), 0);
+    kill_all(&mut app);
+    assert_eq!(bosses(&mut app), 1);
+    assert_eq!(app.report().state, AppState::Ingame);
+
+    kill_all(&mut app);
+    let clear = app.step(1);
+    assert_eq!(clear.state, AppState::StageClear);
+    assert_eq!(clear.stage, 0);
+
+    // the next stage draws from its own stream
+    let mut ticks = 0;
+    while app.step(1).state != AppState::Ingame {
+        ticks += 1;
+        assert!(ticks < 240, "stuck in {:?}", app.report().state);
+    }
+    let report = app.report();
+    assert_eq!(report.stage, 1);
+    assert_eq!(report.enemies, 0);
+    let mut rng = app.app_mut().world_mut().resource_mut::<GameRng>();
+    assert_eq!(rng.seed(), 42);
+    assert_eq!(rng.next_u64(), GameRng::new(43).next_u64());
+}
+
+#[test]
+fn emitter_patterns_fan_out_as_defined() {
+    fn emitter(pattern: BulletPattern) -> BulletEmitter {
+        BulletEmitter::new(&EmitterDefinition { pattern, bullet: BulletType::Fighter, interval: 1.0..1.0 }, 1.0)
+    }
+    fn assert_angles(directions: Vec<Vec2>, degrees: &[f32]) {
+        assert_eq!(directions.len(), degrees.len());
+        for (direction, degrees) in directions.into_iter().zip(degrees) {
+            let expected = Vec2::from_angle(degrees.to_radians());
+            assert!(direction.abs_diff_eq(expected, 1e-4), "{:?} is not at {}", direction, degrees);
+        }
+    }
+    let aim = Vec2::new(1.0, 0.0);
+
+    // a spread fans out evenly around straight down unless it is aimed
+    let mut spread = emitter(BulletPattern::Spread { count: 5, angle: 60.0, aimed: false });
+    assert_angles(spread.directions(aim), &[-120.0, -105.0, -90.0, -75.0, -60.0]);
+    let mut spread = emitter(BulletPattern::Spread { count: 3, angle: 90.0, aimed: true });
+    assert_angles(spread.directions(aim), &[-45.0, 0.0, 45.0]);
+    let mut spread = emitter(BulletPattern::Spread { count: 1, angle: 90.0, aimed: false });
+    assert_angles(spread.directions(aim), &[-90.0]);
+
+    let mut ring = emitter(BulletPattern::Ring { count: 4 });
+    assert_angles(ring.directions(aim), &[0.0, 90.0, 180.0, 270.0]);
+    assert_angles(ring.directions(aim), &[0.0, 90.0, 180.0, 270.0]);
+
+    // a spiral is a ring turned a step further every volley
+    let mut spiral = emitter(BulletPattern::Spiral { count: 3, step: 100.0 });
+    assert_angles(spiral.directions(aim), &[0.0, 120.0, 240.0]);
+    assert_angles(spiral.directions(aim), &[100.0, 220.0, 340.0]);
+    assert_angles(spiral.directions(aim), &[200.0, 320.0, 440.0]);
+    assert_angles(spiral.directions(aim), &[300.0, 420.0, 540.0]);
+    assert_angles(spiral.directions(aim), &[40.0, 160.0, 280.0]);
+}
+
+#[test]
+fn highscore_kills_are_listed_by_their_translated_names() {
+    let game = GamePlugin::new().initial_state(AppState::Mainmenu).persistent(false).background(false);
+    let mut app = HeadlessApp::with_plugin(game);
+    app.wait_for_assets();
+    let kills = |name: &str, count| Kills { name: name.to_string(), count, score: 0 };
+    app.app_mut().world_mut().resource_mut::<HighScores>().insert(HighScore {
+        name: "AB".to_string(),
+        score: 10,
+        kills: vec![kills("fighter", 3), kills("drone", 1)],
+    });
+    // the list is built on entering the title screen
+    app.app_mut().world_mut().resource_mut::<NextState<AppState>>().set(AppState::Ingame);
+    app.step(2);
+    app.app_mut().world_mut().resource_mut::<NextState<AppState>>().set(AppState::Mainmenu);
+    app.step(2);
+
+    // an enemy missing from the string table falls back to its id
+    let world = app.app_mut().world_mut();
+    let lines = world.query::<&Text2d>().iter(world).map(|text| text.0.clone()).collect::<Vec<_>>();
+    assert!(lines.iter().any(|line| line.ends_with("戦闘機 x3 drone x1")), "{:?}", lines);
+}
+
+#[test]
+fn hitboxes_follow_rotation_and_scale() {
+    let at = |x: f32, y: f32| Transform::from_xyz(x, y, 0.0);
+    let dot = Hitbox::circle(1.0);
+
+    // a vertical needle turned on its side reaches along x instead of y
+    let needle = Hitbox::capsule(2.0, Vec2::new(0.0, 6.0));
+    let turned = Transform::from_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2));
+    assert!(needle.intersects(&at(0.0, 0.0), &dot, &at(0.0, 8.0)));
+    assert!(!needle.intersects(&turned, &dot, &at(0.0, 8.0)));
+    assert!(needle.intersects(&turned, &dot, &at(8.0, 0.0)));
+
+    // scale grows the shape and its offset
+    let core = Hitbox::circle(2.0).with_offset(Vec2::new(0.0, 4.0));
+    let scaled = Transform::from_scale(Vec3::splat(2.0));
+    assert!(!core.intersects(&at(0.0, 0.0), &dot, &at(0.0, 11.0)));
+    assert!(core.intersects(&scaled, &dot, &at(0.0, 12.0)));
+
+    // boxes turned 45 degrees only touch when their corners really meet
+    let square = Hitbox::rect(Vec2::splat(10.0));
+    let diamond = Transform::from_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4));
+    assert!(!square.intersects(&diamond, &square, &Transform { rotation: diamond.rotation, ..at(13.0, 13.0) }));
+    assert!(square.intersects(&diamond, &square, &at(12.0, 0.0)));
+    assert!(needle.intersects(&at(0.0, 0.0), &square, &Transform { rotation: diamond.rotation, ..at(0.0, 14.0) }));
+
+    // the torpedo is hit along its body rather than a box around its whole sprite
+    let app = HeadlessApp::new();
+    let world = app.app().world();
+    let definitions = world.resource::<EnemyDefinitions>();
+    let (_, torpedo) = definitions.get("torpedo", world.resource::<Assets<EnemyDefinition>>()).unwrap();
+    assert!(matches!(torpedo.hitbox.map(|hitbox| hitbox.shape), Some(Shape::Capsule { .. })));
+}
+
+#[test]
+fn long_runs_reuse_layouts_and_entities() {
+    let mut app = HeadlessApp::new();
+    let start = app.step(1);
+    let hp = start.player.unwrap().hp;
+    let mut peak = 0;
+
+    // a minute of nonstop shooting, topping the player up so the run never ends
+    app.press(KeyCode::Space);
+    for part in 0..12 {
+        let report = app.step(300);
+        let world = app.app_mut().world_mut();
+        for mut player in world.query::<&mut Player>().iter_mut(world) { player.hp = hp }
+
+        assert_eq!(report.state, AppState::Ingame);
+        assert_eq!(report.atlas_layouts, start.atlas_layouts);
+        // once the pools have grown to the busiest moment, hidden entities are handed out again
+        if part < 6 { peak = peak.max(report.pooled) } else { assert!(report.pooled <= peak) }
+    }
+    assert!(peak > 0);
+}
+
+#[test]
+fn missing_files_stop_on_the_loading_screen() {
+    let game = GamePlugin::new().persistent(false).background(false).mainmenu(false);
+    let mut app = HeadlessApp::with_plugin(game);
+    app.step(1);
+
+    let world = app.app_mut().world_mut();
+    let missing = world.resource::<AssetServer>().load("images/missing.png");
+    world.resource_mut::<GameAssets>().heart = missing;
+
+    for _ in 0..1000 {
+        app.step(1);
+        if !app.app().world().resource::<LoadingProgress>().missing.is_empty() { break }
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+    let world = app.app_mut().world_mut();
+    let progress = world.resource::<LoadingProgress>();
+    assert_eq!(progress.missing.len(), 1);
+    assert!(progress.missing[0].contains("images/missing.png"));
+    assert!(progress.loaded < progress.total);
+    assert_eq!(*world.resource::<State<AppState>>().get(), AppState::Loading);
+    // the error is shown rather than a blank screen
+    let shown = world.query::<&Text2d>().iter(world).any(|text| text.contains("images/missing.png"));
+    assert!(shown);
+}